@@ -1,8 +1,11 @@
 // This file is correct from the previous step. No changes needed.
 // It uses raw pointers and catch_unwind for stability.
+use crate::persistence::DictionaryFormat;
 use crate::ImeEngine;
 use std::ffi::{CStr, CString};
 use libc::c_char;
+use std::fs::File;
+use std::io::{BufReader, Read};
 use std::path::PathBuf;
 use std::ptr;
 use std::panic::{catch_unwind, AssertUnwindSafe};
@@ -76,6 +79,30 @@ pub extern "C" fn nepali_ime_get_suggestions(prefix: *const c_char) -> *mut c_ch
     CString::new(json_string).unwrap().into_raw()
 }
 
+/// Same query as `nepali_ime_get_suggestions`, but returns a JSON array of
+/// `{nepali, score: {context, frequency, edit_distance_penalty, source_prior}}`
+/// objects (each sub-score a `{raw, weight}` pair) so integrators can debug
+/// ranking or show users why a suggestion was ordered where it was.
+#[no_mangle]
+pub extern "C" fn nepali_ime_get_suggestions_explained(prefix: *const c_char) -> *mut c_char {
+    let c_str = unsafe { CStr::from_ptr(prefix) };
+    let roman_prefix = c_str.to_str().unwrap_or("");
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        unsafe {
+            if let Some(engine) = get_engine() {
+                let suggestions = engine.get_suggestions_explained(roman_prefix, 8);
+                return serde_json::to_string(&suggestions).unwrap_or_else(|_| "[]".to_string());
+            }
+        }
+        "[]".to_string()
+    }));
+    let json_string = result.unwrap_or_else(|_| {
+        eprintln!("[Rust FATAL] Panic in get_suggestions_explained.");
+        "[]".to_string()
+    });
+    CString::new(json_string).unwrap().into_raw()
+}
+
 #[no_mangle]
 pub extern "C" fn nepali_ime_confirm_word(roman: *const c_char, nepali: *const c_char) {
     let roman_str = unsafe { CStr::from_ptr(roman) }.to_str().unwrap_or("");
@@ -87,6 +114,105 @@ pub extern "C" fn nepali_ime_confirm_word(roman: *const c_char, nepali: *const c
     }
 }
 
+/// Bulk-imports an external dictionary at `path` into the running engine.
+/// A `.dict` extension is treated as a Hunspell-style word list, paired with
+/// a sibling `.info` frequency file of the same stem if one exists; any
+/// other extension is treated as a plain `roman<TAB>nepali<TAB>frequency`
+/// frequency list. Returns the number of words imported, or -1 on failure.
+#[no_mangle]
+pub extern "C" fn nepali_ime_import_dictionary(path: *const c_char) -> i64 {
+    let path_str = unsafe { CStr::from_ptr(path) }.to_str().unwrap_or("");
+    let result = catch_unwind(AssertUnwindSafe(|| -> i64 {
+        unsafe {
+            let Some(engine) = get_engine_mut() else { return -1 };
+            let path_buf = PathBuf::from(path_str);
+            let Ok(file) = File::open(&path_buf) else { return -1 };
+            let reader = BufReader::new(file);
+
+            let format = if path_buf.extension().and_then(|ext| ext.to_str()) == Some("dict") {
+                let info_path = path_buf.with_extension("info");
+                let mut info = String::new();
+                if let Ok(mut info_file) = File::open(&info_path) {
+                    let _ = info_file.read_to_string(&mut info);
+                }
+                DictionaryFormat::Hunspell { info }
+            } else {
+                DictionaryFormat::FrequencyList
+            };
+
+            match engine.import_dictionary(reader, format, false) {
+                Ok(count) => count as i64,
+                Err(_) => -1,
+            }
+        }
+    }));
+    result.unwrap_or_else(|_| {
+        eprintln!("[Rust FATAL] Panic in import_dictionary.");
+        -1
+    })
+}
+
+/// Transliterates a whole phrase (as opposed to a single preedit word),
+/// passing already-Devanagari and non-letter spans (spaces, punctuation,
+/// digits) through untouched. See `ImeEngine::transliterate_phrase`.
+#[no_mangle]
+pub extern "C" fn nepali_ime_transliterate_phrase(phrase: *const c_char) -> *mut c_char {
+    let c_str = unsafe { CStr::from_ptr(phrase) };
+    let roman_phrase = c_str.to_str().unwrap_or("");
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        unsafe {
+            if let Some(engine) = get_engine_mut() {
+                return engine.transliterate_phrase(roman_phrase);
+            }
+        }
+        String::new()
+    }));
+    let transliterated = result.unwrap_or_else(|_| {
+        eprintln!("[Rust FATAL] Panic in transliterate_phrase.");
+        String::new()
+    });
+    CString::new(transliterated).unwrap().into_raw()
+}
+
+/// Reverse-transliterates Devanagari text back to its Roman spelling. See
+/// `RomanizationEngine::detransliterate`.
+#[no_mangle]
+pub extern "C" fn nepali_ime_detransliterate(devanagari: *const c_char) -> *mut c_char {
+    let c_str = unsafe { CStr::from_ptr(devanagari) };
+    let nepali_text = c_str.to_str().unwrap_or("");
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        unsafe {
+            if let Some(engine) = get_engine() {
+                return engine.romanizer.detransliterate(nepali_text);
+            }
+        }
+        String::new()
+    }));
+    let roman = result.unwrap_or_else(|_| {
+        eprintln!("[Rust FATAL] Panic in detransliterate.");
+        String::new()
+    });
+    CString::new(roman).unwrap().into_raw()
+}
+
+/// Tunes the beam/weight/n-best pruning `get_suggestions` applies. Pass a
+/// negative number for any knob the caller wants left unset (`None`).
+#[no_mangle]
+pub extern "C" fn nepali_ime_set_suggestion_config(n_best: i32, max_weight: f32, beam: f32, node_pool_size: i32) {
+    let _ = catch_unwind(AssertUnwindSafe(|| {
+        unsafe {
+            if let Some(engine) = get_engine_mut() {
+                let mut config = crate::core::config::SuggestionConfig::new();
+                if n_best >= 0 { config = config.with_n_best(n_best as usize); }
+                if max_weight.is_finite() && max_weight >= 0.0 { config = config.with_max_weight(max_weight); }
+                if beam.is_finite() && beam >= 0.0 { config = config.with_beam(beam); }
+                if node_pool_size >= 0 { config = config.with_node_pool_size(node_pool_size as usize); }
+                engine.suggestion_config = config;
+            }
+        }
+    }));
+}
+
 #[no_mangle]
 pub extern "C" fn nepali_ime_free_string(s: *mut c_char) {
     if !s.is_null() { unsafe { let _ = CString::from_raw(s); } }
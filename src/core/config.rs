@@ -0,0 +1,114 @@
+// File: src/core/config.rs
+use crate::core::ranking::Candidate;
+
+/// Tunable knobs for `ImeEngine::get_suggestions`'s candidate gathering and
+/// pruning, modeled on a speller config: callers can trade recall for
+/// latency instead of always materializing every literal/fuzzy variant and
+/// truncating only at the very end.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SuggestionConfig {
+    /// Hard cap on the number of suggestions returned, applied after pruning.
+    /// `None` defers entirely to the `count` argument of `get_suggestions`.
+    pub n_best: Option<usize>,
+    /// Discard any candidate whose `Candidate::cost()` exceeds this value outright.
+    pub max_weight: Option<f32>,
+    /// Relative cutoff: after the minimum cost among surviving candidates is
+    /// known, drop anything costing more than `min_cost + beam`.
+    pub beam: Option<f32>,
+    /// Caps how many intermediate fuzzy nodes `SymSpell` may expand per
+    /// lookup, so a worst-case query can't blow up memory/latency.
+    pub node_pool_size: Option<usize>,
+}
+
+impl SuggestionConfig {
+    pub fn new() -> Self {
+        Self { n_best: None, max_weight: None, beam: None, node_pool_size: None }
+    }
+
+    pub fn with_n_best(mut self, n_best: usize) -> Self {
+        self.n_best = Some(n_best);
+        self
+    }
+
+    pub fn with_max_weight(mut self, max_weight: f32) -> Self {
+        self.max_weight = Some(max_weight);
+        self
+    }
+
+    pub fn with_beam(mut self, beam: f32) -> Self {
+        self.beam = Some(beam);
+        self
+    }
+
+    pub fn with_node_pool_size(mut self, node_pool_size: usize) -> Self {
+        self.node_pool_size = Some(node_pool_size);
+        self
+    }
+}
+
+impl Default for SuggestionConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Applies `max_weight` and `beam` pruning to a gathered candidate set, in
+/// that order: `max_weight` is an absolute ceiling, `beam` is a relative
+/// window around whatever the cheapest surviving candidate turns out to be.
+pub fn prune(candidates: &mut Vec<Candidate>, config: &SuggestionConfig) {
+    if let Some(max_weight) = config.max_weight {
+        candidates.retain(|c| c.cost() <= max_weight);
+    }
+
+    if let Some(beam) = config.beam {
+        let min_cost = candidates.iter().map(Candidate::cost).fold(f32::INFINITY, f32::min);
+        if min_cost.is_finite() {
+            candidates.retain(|c| c.cost() <= min_cost + beam);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(nepali: &str, score: u64, edit_distance: usize) -> Candidate {
+        Candidate { word_id: None, nepali: nepali.to_string(), score, edit_distance: Some(edit_distance) }
+    }
+
+    #[test]
+    fn max_weight_and_beam_combine() {
+        let mut candidates = vec![
+            candidate("a", 0, 0), // cost 0.0
+            candidate("b", 0, 1), // cost 1.0
+            candidate("c", 0, 3), // cost 3.0
+        ];
+        let config = SuggestionConfig::new().with_max_weight(2.0).with_beam(0.5);
+
+        prune(&mut candidates, &config);
+
+        // max_weight drops "c" (cost 3.0); beam then drops "b" (cost 1.0 is
+        // more than 0.5 past "a"'s now-cheapest cost of 0.0).
+        assert_eq!(candidates.iter().map(|c| c.nepali.as_str()).collect::<Vec<_>>(), vec!["a"]);
+    }
+
+    #[test]
+    fn max_weight_can_prune_every_candidate() {
+        let mut candidates = vec![candidate("a", 0, 0), candidate("b", 10, 0)];
+        let config = SuggestionConfig::new().with_max_weight(-100.0).with_beam(1.0);
+
+        prune(&mut candidates, &config);
+
+        assert!(candidates.is_empty());
+    }
+
+    #[test]
+    fn prune_on_an_empty_candidate_set_is_a_no_op() {
+        let mut candidates: Vec<Candidate> = Vec::new();
+        let config = SuggestionConfig::new().with_max_weight(1.0).with_beam(1.0);
+
+        prune(&mut candidates, &config);
+
+        assert!(candidates.is_empty());
+    }
+}
@@ -1,8 +1,17 @@
 // File: src/core/context.rs
 use crate::core::types::WordId;
 use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "std")]
 use std::collections::{HashMap, VecDeque};
 
+#[cfg(not(feature = "std"))]
+use alloc::collections::VecDeque;
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use hashbrown::HashMap;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContextModel {
     window_size: usize,
@@ -33,20 +42,19 @@ impl ContextModel {
         self.history.push_back(word_id);
     }
 
-    /// Re-ranks a list of suggestions based on the current context.
-    /// Suggestions that form common bigrams with the previous word get a score boost.
-    pub fn rerank_suggestions(&self, suggestions: &mut Vec<(WordId, u64)>) {
-        if let Some(&prev_word_id) = self.history.back() {
-            for (word_id, score) in suggestions.iter_mut() {
-                if let Some(&bigram_count) = self.bigrams.get(&(prev_word_id, *word_id)) {
-                    // Simple boost: add a factor of the bigram count.
-                    // A more advanced model might use logarithms or smoothed probabilities.
-                    let boost = (bigram_count as f64).log2() * 10.0;
-                    *score += boost as u64;
-                }
-            }
-            // Re-sort the suggestions based on the new boosted scores
-            suggestions.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+    /// The `WordId`s confirmed so far, oldest first, used by ranking criteria
+    /// that want more than just the immediately preceding word.
+    pub fn history(&self) -> Vec<WordId> {
+        self.history.iter().copied().collect()
+    }
+
+    /// The bigram boost `word_id` would receive for following `prev_word_id`:
+    /// `log2(bigram_count) * 10`, or 0 if the pair was never seen. A more
+    /// advanced model might use smoothed probabilities instead.
+    pub fn bigram_boost(&self, prev_word_id: WordId, word_id: WordId) -> u64 {
+        match self.bigrams.get(&(prev_word_id, word_id)) {
+            Some(&bigram_count) => ((bigram_count as f64).log2() * 10.0) as u64,
+            None => 0,
         }
     }
 }
\ No newline at end of file
@@ -38,22 +38,121 @@ enum MapKind {
     Consonant,
 }
 
+/// Selects which Roman<->Devanagari token tables `RomanizationEngine` builds.
+/// The FST logic in `transliterate_base`/`detransliterate` is identical
+/// either way; only the tables (and so `max_token_len`) change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Scheme {
+    /// The default, many-roman-spellings-per-sound scheme documented in
+    /// `RomanizationEngine::new` - optimized for fast, intuitive typing over
+    /// strict invertibility.
+    #[default]
+    Ergonomic,
+    /// SLP1 (Sanskrit Library Phonetic), the transliteration encoding used by
+    /// the Sanskrit Wiktionary `sa-utilities` module: a strict one-to-one
+    /// ASCII encoding of Devanagari, so round-tripping through
+    /// `detransliterate` is lossless and `transliterate_base` never needs
+    /// `generate_candidates`' heuristics.
+    Slp1,
+}
+
+/// The three Roman -> Devanagari forward lookup tables a `Scheme` builds:
+/// consonants, vowels (full independent form and matra), and symbols. Named
+/// here (rather than inlined as a bare tuple) so `ergonomic_tables`/
+/// `slp1_tables`'s return type doesn't trip `clippy::type_complexity`.
+type ForwardTables = (
+    HashMap<&'static str, &'static str>,
+    HashMap<&'static str, (&'static str, &'static str)>,
+    HashMap<&'static str, &'static str>,
+);
+
+/// The four Devanagari -> Roman reverse lookup tables `build_reverse_tables`
+/// produces: consonants, independent vowels, matras, and symbols. See
+/// `ForwardTables` for why this is a named alias rather than a bare tuple.
+type ReverseTables = (
+    HashMap<&'static str, &'static str>,
+    HashMap<&'static str, &'static str>,
+    HashMap<&'static str, &'static str>,
+    HashMap<&'static str, &'static str>,
+);
+
 pub struct RomanizationEngine {
+    scheme: Scheme,
     consonants: HashMap<&'static str, &'static str>,
     vowels: HashMap<&'static str, (&'static str, &'static str)>, // (Full Vowel, Matra)
     symbols: HashMap<&'static str, &'static str>,
     max_token_len: usize,
+    // Reverse (Devanagari -> Roman) tables for `detransliterate`. See their
+    // construction in `build_reverse_tables` for why they're separate from
+    // the maps above.
+    reverse_consonants: HashMap<&'static str, &'static str>,
+    reverse_vowels_full: HashMap<&'static str, &'static str>,
+    reverse_matras: HashMap<&'static str, &'static str>,
+    reverse_symbols: HashMap<&'static str, &'static str>,
+    /// Optional word-frequency lexicon (Devanagari form -> count) used to
+    /// re-rank `generate_candidates`' output so an attested word floats
+    /// above a phonetically-plausible-but-unused one. See `with_lexicon`.
+    lexicon: Option<HashMap<String, u32>>,
 }
 
 impl RomanizationEngine {
     pub fn new() -> Self {
-        // --- PRINCIPLED & ERGONOMIC KEY MAPPINGS ---
-        // These mappings are designed to be intuitive, fast, and consistent.
-        // - Aspiration is consistently marked with 'h' (k -> क, kh -> ख).
-        // - Retroflex consonants use Capital letters (t -> त, T -> ट).
-        // - Vowel length is achieved by doubling (i -> इ, ii -> ई).
-        // - Common aliases are provided for user convenience (f/ph, ee/ii).
+        Self::with_scheme(Scheme::Ergonomic)
+    }
+
+    /// Builds an engine using `scheme`'s token tables instead of the default
+    /// `Scheme::Ergonomic` ones. See `Scheme` for what each one trades off.
+    pub fn with_scheme(scheme: Scheme) -> Self {
+        let (consonants, vowels, symbols) = match scheme {
+            Scheme::Ergonomic => Self::ergonomic_tables(),
+            Scheme::Slp1 => Self::slp1_tables(),
+        };
+
+        let max_token_len = consonants.keys()
+            .chain(vowels.keys())
+            .chain(symbols.keys())
+            .map(|s| s.len())
+            .max()
+            .unwrap_or(4);
+
+        let (reverse_consonants, reverse_vowels_full, reverse_matras, reverse_symbols) =
+            Self::build_reverse_tables(scheme, &consonants, &vowels, &symbols);
+
+        Self {
+            scheme,
+            consonants, vowels, symbols, max_token_len,
+            reverse_consonants, reverse_vowels_full, reverse_matras, reverse_symbols,
+            lexicon: None,
+        }
+    }
+
+    /// Attaches a word-frequency lexicon (Devanagari form -> count) that
+    /// `generate_candidates` uses to re-rank its output so an attested word
+    /// floats to the top instead of staying in fixed heuristic-insertion
+    /// order. Purely additive: with no lexicon (the default), ranking is
+    /// unchanged.
+    pub fn with_lexicon(mut self, lexicon: HashMap<String, u32>) -> Self {
+        self.lexicon = Some(lexicon);
+        self
+    }
+
+    /// Same as `with_lexicon`, but mutates an already-constructed engine in
+    /// place instead of consuming `self` - for a caller (e.g. `ImeEngine`)
+    /// that rebuilds the lexicon as its dictionary grows, rather than
+    /// attaching it once at construction time.
+    pub fn set_lexicon(&mut self, lexicon: HashMap<String, u32>) {
+        self.lexicon = Some(lexicon);
+    }
 
+    /// The default scheme's forward tables.
+    ///
+    /// --- PRINCIPLED & ERGONOMIC KEY MAPPINGS ---
+    /// These mappings are designed to be intuitive, fast, and consistent.
+    /// - Aspiration is consistently marked with 'h' (k -> क, kh -> ख).
+    /// - Retroflex consonants use Capital letters (t -> त, T -> ट).
+    /// - Vowel length is achieved by doubling (i -> इ, ii -> ई).
+    /// - Common aliases are provided for user convenience (f/ph, ee/ii).
+    fn ergonomic_tables() -> ForwardTables {
         let consonants: HashMap<_, _> = [
             // Standard consonants
             ("k", "क"), ("kh", "ख"), ("g", "ग"), ("gh", "घ"), ("ng", "ङ"),
@@ -134,28 +233,144 @@ impl RomanizationEngine {
             // Punctuation
             (".", "।"), ("..", "।।"), ("...", "..."),
             ("?", "?"), ("!", "!"), (",", ","), (";", ";"), (":", ":"),
-            
+
             // Special symbols
             ("OM", "ॐ"), ("Om", "ॐ"), ("AUM", "ॐ"),
             ("'", "ऽ"), ("@", "ॐ"),
-            
+
             // Devanagari digits
             ("0", "०"), ("1", "१"), ("2", "२"), ("3", "३"), ("4", "४"),
             ("5", "५"), ("6", "६"), ("7", "७"), ("8", "८"), ("9", "९"),
-            
+
             // Additional marks
             ("|", "।"), ("||", "।।"),
             ("_", "\u{094D}"), // Explicit virama/halanta
         ].iter().cloned().collect();
 
-        let max_token_len = consonants.keys()
-            .chain(vowels.keys())
-            .chain(symbols.keys())
-            .map(|s| s.len())
-            .max()
-            .unwrap_or(4);
+        (consonants, vowels, symbols)
+    }
 
-        Self { consonants, vowels, symbols, max_token_len }
+    /// SLP1 (Sanskrit Library Phonetic) forward tables: a strict one-to-one
+    /// ASCII encoding, so (unlike `ergonomic_tables`) every entry here is the
+    /// *only* Roman spelling for its Devanagari unit - no aliases.
+    fn slp1_tables() -> ForwardTables {
+        let consonants: HashMap<_, _> = [
+            ("k", "क"), ("K", "ख"), ("g", "ग"), ("G", "घ"), ("N", "ङ"),
+            ("c", "च"), ("C", "छ"), ("j", "ज"), ("J", "झ"), ("Y", "ञ"),
+            ("w", "ट"), ("W", "ठ"), ("q", "ड"), ("Q", "ढ"), ("R", "ण"),
+            ("t", "त"), ("T", "थ"), ("d", "द"), ("D", "ध"), ("n", "न"),
+            ("p", "प"), ("P", "फ"), ("b", "ब"), ("B", "भ"), ("m", "म"),
+            ("y", "य"), ("r", "र"), ("l", "ल"), ("v", "व"),
+            ("z", "श"), ("S", "ष"), ("s", "स"), ("h", "ह"),
+            ("L", "ळ"),
+        ].iter().cloned().collect();
+
+        let vowels: HashMap<_, _> = [
+            ("a", ("अ", "")), // The matra for 'a' is the absence of a virama.
+            ("A", ("आ", "ा")),
+            ("i", ("इ", "ि")), ("I", ("ई", "ी")),
+            ("u", ("उ", "ु")), ("U", ("ऊ", "ू")),
+            ("f", ("ऋ", "ृ")), ("F", ("ॠ", "ॄ")),
+            ("x", ("ऌ", "ॢ")), ("X", ("ॡ", "ॣ")),
+            ("e", ("ए", "े")), ("E", ("ऐ", "ै")),
+            ("o", ("ओ", "ो")), ("O", ("औ", "ौ")),
+            ("M", ("अं", "ं")), // Anusvara
+            ("H", ("अः", "ः")), // Visarga
+            ("~", ("अँ", "ँ")), // Candrabindu
+        ].iter().cloned().collect();
+
+        let symbols: HashMap<_, _> = [
+            (".", "।"), ("..", "।।"), ("...", "..."),
+            ("?", "?"), ("!", "!"), (",", ","), (";", ";"), (":", ":"),
+            ("'", "ऽ"), // Avagraha
+            ("0", "०"), ("1", "१"), ("2", "२"), ("3", "३"), ("4", "४"),
+            ("5", "५"), ("6", "६"), ("7", "७"), ("8", "८"), ("9", "९"),
+        ].iter().cloned().collect();
+
+        (consonants, vowels, symbols)
+    }
+
+    /// Builds `detransliterate`'s reverse tables for `scheme`.
+    ///
+    /// `Scheme::Slp1` is one-to-one by construction, so its reverse tables
+    /// are just the forward ones inverted - there's no ambiguity to resolve.
+    /// `Scheme::Ergonomic` has several Roman spellings per Devanagari unit
+    /// (e.g. "w" and "v" both give व), so its reverse tables can't be derived
+    /// that way; each is its own explicit, ordered list of only the
+    /// canonical Roman spelling per Devanagari unit - the standard one from
+    /// `ergonomic_tables`, not an alias.
+    fn build_reverse_tables(
+        scheme: Scheme,
+        consonants: &HashMap<&'static str, &'static str>,
+        vowels: &HashMap<&'static str, (&'static str, &'static str)>,
+        symbols: &HashMap<&'static str, &'static str>,
+    ) -> ReverseTables {
+        if scheme == Scheme::Slp1 {
+            let reverse_consonants = consonants.iter().map(|(&roman, &nepali)| (nepali, roman)).collect();
+            let reverse_symbols = symbols.iter().map(|(&roman, &nepali)| (nepali, roman)).collect();
+
+            let mut reverse_vowels_full = HashMap::new();
+            let mut reverse_matras = HashMap::new();
+            for (&roman, &(full, matra)) in vowels {
+                reverse_vowels_full.insert(full, roman);
+                if !matra.is_empty() {
+                    reverse_matras.insert(matra, roman);
+                }
+            }
+
+            return (reverse_consonants, reverse_vowels_full, reverse_matras, reverse_symbols);
+        }
+
+        let reverse_consonants: HashMap<&'static str, &'static str> = [
+            ("क", "k"), ("ख", "kh"), ("ग", "g"), ("घ", "gh"), ("ङ", "ng"),
+            ("च", "ch"), ("छ", "chh"), ("ज", "j"), ("झ", "jh"), ("ञ", "ny"),
+            ("ट", "T"), ("ठ", "Th"), ("ड", "D"), ("ढ", "Dh"), ("ण", "N"),
+            ("त", "t"), ("थ", "th"), ("द", "d"), ("ध", "dh"), ("न", "n"),
+            ("प", "p"), ("फ", "ph"), ("ब", "b"), ("भ", "bh"), ("म", "m"),
+            ("य", "y"), ("र", "r"), ("ल", "l"), ("व", "w"),
+            ("श", "sh"), ("ष", "S"), ("स", "s"), ("ह", "h"),
+            ("क्ष", "ksh"), ("त्र", "tra"), ("ज्ञ", "jnya"),
+            ("क़", "q"), ("ख़", "K"), ("ग़", "G"), ("ज़", "z"), ("झ़", "Z"),
+            ("ढ़", "Rh"), ("ड़", "Rf"), ("फ़", "f"),
+            ("ळ", "L"), ("ऩ", "nN"), ("ऱ", "rR"), ("ऴ", "lL"),
+        ].iter().cloned().collect();
+
+        // Independent vowel form -> Roman, used when the vowel stands on its
+        // own (word-initial, or following another vowel) rather than as a
+        // matra attached to a consonant.
+        let reverse_vowels_full: HashMap<&'static str, &'static str> = [
+            ("अ", "a"), ("आ", "aa"), ("इ", "i"), ("ई", "ii"), ("उ", "u"),
+            ("ऊ", "uu"), ("ए", "e"), ("ऐ", "ai"), ("ओ", "o"), ("औ", "au"),
+            ("ऋ", "ri"), ("ॠ", "rii"), ("ऌ", "li"), ("ॡ", "lii"),
+            ("अं", "M"), ("अः", "H"), ("अँ", "~"),
+        ].iter().cloned().collect();
+
+        // Vowel sign (matra) -> Roman, used right after a consonant. The
+        // inherent "a" has no matra glyph at all, so it isn't looked up here
+        // - `detransliterate` falls back to it whenever a consonant is
+        // followed by neither a matra nor a virama.
+        let reverse_matras: HashMap<&'static str, &'static str> = [
+            ("ा", "aa"), ("ि", "i"), ("ी", "ii"), ("ु", "u"), ("ू", "uu"),
+            ("े", "e"), ("ै", "ai"), ("ो", "o"), ("ौ", "au"),
+            ("ृ", "ri"), ("ॄ", "rii"), ("ॢ", "li"), ("ॣ", "lii"),
+            ("ं", "M"), ("ः", "H"), ("ँ", "~"),
+        ].iter().cloned().collect();
+
+        let reverse_symbols: HashMap<&'static str, &'static str> = [
+            ("।", "."), ("।।", ".."), ("...", "..."),
+            ("?", "?"), ("!", "!"), (",", ","), (";", ";"), (":", ":"),
+            ("ॐ", "OM"), ("ऽ", "'"),
+            ("०", "0"), ("१", "1"), ("२", "2"), ("३", "3"), ("४", "4"),
+            ("५", "5"), ("६", "6"), ("७", "7"), ("८", "8"), ("९", "9"),
+        ].iter().cloned().collect();
+
+        (reverse_consonants, reverse_vowels_full, reverse_matras, reverse_symbols)
+    }
+
+    /// The scheme this engine was built with (`Scheme::Ergonomic` unless
+    /// constructed via `with_scheme`).
+    pub fn scheme(&self) -> Scheme {
+        self.scheme
     }
 
     /// Generates the single most likely, deterministic transliteration.
@@ -163,7 +378,37 @@ impl RomanizationEngine {
     pub fn transliterate_primary(&self, roman: &str) -> String {
         if roman.is_empty() { return String::new(); }
         // By default, apply schwa deletion at the end of words (e.g., "ram" -> "राम").
-        self.transliterate_base(roman, true)
+        self.transliterate_base(roman, true, false)
+    }
+
+    /// Transliterates `roman`, first inferring medial schwa placement per
+    /// standard Hindi/Nepali phonotactics (see `expand_medial_schwa`), so a
+    /// bare consonant run like "kml" comes out "कमल" instead of the single
+    /// conjunct `transliterate_primary` would build from it. Off by default
+    /// since it changes how a consonant run a user typed *as* a deliberate
+    /// conjunct gets read - opt in when the input is expected to omit
+    /// vowels, e.g. transliterating names or dictionary headwords typed
+    /// without diacritics.
+    ///
+    /// Known limitation: this only places the *implicit* schwa per the
+    /// VC_CV rule - it doesn't infer the colloquial vowel-lengthening some
+    /// words carry by convention (e.g. "namskar" comes out "नमस्कर", not
+    /// the long-vowel "नमस्कार" a Nepali speaker would expect); type the
+    /// long vowel explicitly ("namaskAr") to get that spelling.
+    pub fn transliterate_natural(&self, roman: &str) -> String {
+        if roman.is_empty() { return String::new(); }
+        self.transliterate_base(roman, true, true)
+    }
+
+    /// `transliterate_primary`'s alternate form: keeps a trailing bare
+    /// consonant's halanta instead of deleting it, so the word-final schwa
+    /// stays silent rather than implicit (e.g. "kam" -> "कम्" rather than
+    /// "कम"). Used by `ImeEngine::transliterate_phrase` for an ALL-CAPS
+    /// roman token, since Devanagari carries no case distinction of its own
+    /// for capitalization to otherwise signal.
+    pub fn transliterate_alternate(&self, roman: &str) -> String {
+        if roman.is_empty() { return String::new(); }
+        self.transliterate_base(roman, false, false)
     }
 
     /// Generates a list of likely candidates to handle phonetic ambiguity.
@@ -178,7 +423,7 @@ impl RomanizationEngine {
         // Heuristic 1: Handle final 'a' ambiguity (e.g., "rama" -> "राम" vs "रामा").
         // The primary transliteration assumes schwa deletion. This variant preserves the 'a'.
         if roman.ends_with('a') && !roman.ends_with("aa") {
-            let variant = self.transliterate_base(roman, false);
+            let variant = self.transliterate_base(roman, false, false);
             if variant != primary {
                 candidates.insert(variant);
             }
@@ -278,6 +523,16 @@ impl RomanizationEngine {
                 result.push(cand);
             }
         }
+
+        // Dictionary-backed re-ranking: when a lexicon is attached, float
+        // attested words above unattested ones, highest frequency first.
+        // `sort_by_key` is stable, so candidates that tie (including the
+        // common case of no lexicon entry for either, both 0) keep the
+        // heuristics' original insertion order.
+        if let Some(lexicon) = &self.lexicon {
+            result.sort_by_key(|candidate| std::cmp::Reverse(lexicon.get(candidate).copied().unwrap_or(0)));
+        }
+
         result
     }
 
@@ -292,8 +547,94 @@ impl RomanizationEngine {
         None
     }
 
+    /// Infers medial schwa placement over a vowel-sparse consonant skeleton
+    /// (e.g. "kml", "namskar") and returns an expanded Roman string with an
+    /// explicit "a" inserted wherever the schwa should be retained, so the
+    /// normal FST walk below builds the right syllable breaks/conjuncts
+    /// without needing to know about schwa deletion itself.
+    ///
+    /// Implements the standard right-to-left schwa-deletion rule: a bare
+    /// consonant's inherent schwa is deleted (left to form a conjunct with
+    /// the next consonant) when it is preceded by another letter (not
+    /// word-initial) *and* the next consonant itself surfaces its own vowel,
+    /// whether an explicit one or a retained schwa, resolved recursively
+    /// since we scan right to left. A word-initial bare consonant always
+    /// retains its schwa (Hindi/Nepali don't delete the first syllable's
+    /// vowel), and the word-final one always deletes it, matching
+    /// `force_schwa_deletion`'s existing treatment of a trailing consonant
+    /// (spelled the same bare way either way, so this choice doesn't change
+    /// output by itself; it only matters as the base case for the consonant
+    /// to its left).
+    fn expand_medial_schwa(&self, roman: &str) -> String {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Kind { Consonant, Vowel, Other }
+
+        let mut tokens: Vec<(&str, Kind)> = Vec::new();
+        let mut input = roman;
+        while !input.is_empty() {
+            let chunk = &input[..input.len().min(self.max_token_len)];
+            if let Some((token, _, kind)) = self.match_longest(chunk) {
+                let kind = match kind {
+                    MapKind::Consonant => Kind::Consonant,
+                    MapKind::Vowel => Kind::Vowel,
+                    MapKind::Symbol => Kind::Other,
+                };
+                tokens.push((token, kind));
+                input = &input[token.len()..];
+            } else {
+                let ch_len = input.chars().next().unwrap().len_utf8();
+                tokens.push((&input[..ch_len], Kind::Other));
+                input = &input[ch_len..];
+            }
+        }
+
+        let is_bare = |tokens: &[(&str, Kind)], i: usize| {
+            tokens[i].1 == Kind::Consonant && !matches!(tokens.get(i + 1), Some((_, Kind::Vowel)))
+        };
+
+        let mut deleted = vec![false; tokens.len()];
+        for i in (0..tokens.len()).rev() {
+            if !is_bare(&tokens, i) {
+                continue;
+            }
+            let has_next_consonant = matches!(tokens.get(i + 1), Some((_, Kind::Consonant)));
+            if !has_next_consonant {
+                deleted[i] = true; // Word-final bare consonant.
+                continue;
+            }
+            let left_context = i > 0;
+            let right_context = if is_bare(&tokens, i + 1) { !deleted[i + 1] } else { true };
+            deleted[i] = left_context && right_context;
+        }
+
+        let mut expanded = String::with_capacity(roman.len() + tokens.len());
+        for (i, (text, kind)) in tokens.iter().enumerate() {
+            expanded.push_str(text);
+            if *kind == Kind::Consonant && is_bare(&tokens, i) && !deleted[i] {
+                expanded.push('a');
+            }
+        }
+        expanded
+    }
+
+    /// True for the matra of an anusvara, visarga, or candrabindu (U+0900-
+    /// U+0903) - nasalization/aspiration marks that modify whatever vowel a
+    /// syllable already has, as opposed to an ordinary vowel sign that forms
+    /// a new syllable nucleus of its own.
+    fn is_modifier_vowel(matra: &str) -> bool {
+        matches!(matra, "\u{0900}" | "\u{0901}" | "\u{0902}" | "\u{0903}")
+    }
+
     /// The core FST-based transliteration logic.
-    fn transliterate_base(&self, roman: &str, force_schwa_deletion: bool) -> String {
+    fn transliterate_base(&self, roman: &str, force_schwa_deletion: bool, medial_schwa_deletion: bool) -> String {
+        let expanded;
+        let roman = if medial_schwa_deletion {
+            expanded = self.expand_medial_schwa(roman);
+            expanded.as_str()
+        } else {
+            roman
+        };
+
         let mut result = String::with_capacity(roman.len() * 3);
         let mut state = State::Start;
         const HALANTA: &str = "\u{094d}";
@@ -301,10 +642,10 @@ impl RomanizationEngine {
 
         while !input.is_empty() {
             let chunk = &input[..input.len().min(self.max_token_len)];
-            
+
             if let Some((token, match_result, _kind)) = self.match_longest(chunk) {
                 match state {
-                    State::Start | State::Syllable => match match_result {
+                    State::Start => match match_result {
                         MatchResult::Consonant(nepali) => {
                             result.push_str(nepali);
                             result.push_str(HALANTA);
@@ -319,6 +660,32 @@ impl RomanizationEngine {
                             state = State::Start;
                         }
                     },
+                    State::Syllable => match match_result {
+                        MatchResult::Consonant(nepali) => {
+                            result.push_str(nepali);
+                            result.push_str(HALANTA);
+                            state = State::Halanta;
+                        }
+                        MatchResult::Vowel { full, matra } => {
+                            // Anusvara/visarga/candrabindu modify the syllable
+                            // that's already been formed - whether by an
+                            // explicit matra or the implicit schwa of the
+                            // preceding consonant - rather than starting a
+                            // fresh independent vowel mid-word. Emitting
+                            // `full` here (as if nothing preceded) is what
+                            // turned "rAmaH" into "रामअः" instead of "रामः".
+                            if Self::is_modifier_vowel(matra) {
+                                result.push_str(matra);
+                            } else {
+                                result.push_str(full);
+                            }
+                            state = State::Syllable;
+                        }
+                        MatchResult::Symbol(nepali) => {
+                            result.push_str(nepali);
+                            state = State::Start;
+                        }
+                    },
                     State::Halanta => match match_result {
                         MatchResult::Consonant(nepali) => {
                             // MODIFICATION 2: Add special grammatical rules for ya-phala and rakar.
@@ -370,16 +737,260 @@ impl RomanizationEngine {
         result
     }
 
-    /// Implements Longest Prefix Match (LPM) and categorizes the match.
+    /// Reverse transliteration: walks a Devanagari string and emits Roman,
+    /// the mirror image of `transliterate_base`'s state machine. Reading a
+    /// consonant requires looking one cluster ahead: a following virama
+    /// (U+094D) means it's a conjunct member with no vowel of its own; a
+    /// following vowel matra supplies its vowel; a following anusvara/
+    /// visarga/candrabindu matra (U+0900-U+0903) modifies the implicit
+    /// schwa rather than replacing it, so `a` is still emitted before it;
+    /// anything else (another consonant, a symbol, or the end of the
+    /// string) means the consonant carries its bare implicit schwa, so `a`
+    /// alone is emitted for it. Independent vowels and symbols map directly
+    /// with no lookahead needed. A modifier matra not already consumed by a
+    /// consonant's lookahead (e.g. one following a vowel matra, as in "kiH")
+    /// still maps back to its Roman form on its own. A stray virama with no
+    /// consonant before it is dropped; any other character this table
+    /// doesn't recognize (other combining marks, ZWNJ/ZWJ, other scripts)
+    /// passes through unchanged.
+    pub fn detransliterate(&self, devanagari: &str) -> String {
+        const HALANTA: char = '\u{094d}';
+        let mut result = String::with_capacity(devanagari.len() * 2);
+        let chars: Vec<char> = devanagari.chars().collect();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let current = chars[i].to_string();
+
+            if let Some(&roman) = self.reverse_consonants.get(current.as_str()) {
+                result.push_str(roman);
+                match chars.get(i + 1) {
+                    Some(&next) if next == HALANTA => {
+                        i += 2; // Conjunct member: no vowel, virama consumed.
+                    }
+                    Some(next) if self.reverse_matras.contains_key(next.to_string().as_str()) => {
+                        let next = next.to_string();
+                        if Self::is_modifier_vowel(next.as_str()) {
+                            // The modifier attaches to the implicit schwa,
+                            // it doesn't replace it (e.g. म + ः -> "maH").
+                            result.push('a');
+                        }
+                        result.push_str(self.reverse_matras[next.as_str()]);
+                        i += 2;
+                    }
+                    _ => {
+                        result.push('a'); // Implicit schwa.
+                        i += 1;
+                    }
+                }
+            } else if let Some(&roman) = self.reverse_vowels_full.get(current.as_str()) {
+                result.push_str(roman);
+                i += 1;
+            } else if let Some(&roman) = self.reverse_symbols.get(current.as_str()) {
+                result.push_str(roman);
+                i += 1;
+            } else if let Some(&roman) = self.reverse_matras.get(current.as_str()) {
+                // A modifier matra following a vowel matra (e.g. the
+                // visarga in "kiH") isn't consumed by the consonant lookahead
+                // above, but still maps back to its own Roman form.
+                result.push_str(roman);
+                i += 1;
+            } else if chars[i] != HALANTA {
+                // A stray virama with no preceding consonant (already
+                // consumed above otherwise) has nothing to attach to either.
+                result.push(chars[i]);
+                i += 1;
+            } else {
+                i += 1;
+            }
+        }
+
+        result
+    }
+
+    /// Devanagari consonant -> broad IPA phoneme, with no inherent vowel (the
+    /// nucleus is resolved separately per akshara in `ipa_aksharas`).
+    /// Aspiration (ʰ/ʱ) and place-of-articulation diacritics (dental ̪,
+    /// retroflex ʈ/ɖ/ɳ) live inside the phoneme string itself, so a syllable
+    /// boundary inserted between aksharas can never land in the middle of one.
+    fn ipa_consonant_table() -> HashMap<&'static str, &'static str> {
+        [
+            ("क", "k"), ("ख", "kʰ"), ("ग", "ɡ"), ("घ", "ɡʱ"), ("ङ", "ŋ"),
+            ("च", "t͡s"), ("छ", "t͡sʰ"), ("ज", "d͡z"), ("झ", "d͡zʱ"), ("ञ", "ɲ"),
+            ("ट", "ʈ"), ("ठ", "ʈʰ"), ("ड", "ɖ"), ("ढ", "ɖʱ"), ("ण", "ɳ"),
+            ("त", "t̪"), ("थ", "t̪ʰ"), ("द", "d̪"), ("ध", "d̪ʱ"), ("न", "n"),
+            ("प", "p"), ("फ", "pʰ"), ("ब", "b"), ("भ", "bʱ"), ("म", "m"),
+            ("य", "j"), ("र", "ɾ"), ("ल", "l"), ("व", "w"),
+            ("श", "ʃ"), ("ष", "ʃ"), ("स", "s"), ("ह", "ɦ"),
+        ].iter().cloned().collect()
+    }
+
+    /// Independent (word/syllable-initial) Devanagari vowel -> IPA.
+    fn ipa_vowel_full_table() -> HashMap<&'static str, &'static str> {
+        [
+            ("अ", "ə"), ("आ", "ɑː"), ("इ", "i"), ("ई", "iː"), ("उ", "u"),
+            ("ऊ", "uː"), ("ऋ", "r̩"), ("ए", "eː"), ("ऐ", "ɛ"), ("ओ", "oː"),
+            ("औ", "ɔ"),
+        ].iter().cloned().collect()
+    }
+
+    /// Vowel sign (matra) -> IPA, used when the vowel is attached to a
+    /// consonant rather than standing on its own.
+    fn ipa_matra_table() -> HashMap<&'static str, &'static str> {
+        [
+            ("ा", "ɑː"), ("ि", "i"), ("ी", "iː"), ("ु", "u"), ("ू", "uː"),
+            ("ृ", "r̩"), ("े", "eː"), ("ै", "ɛ"), ("ो", "oː"), ("ौ", "ɔ"),
+        ].iter().cloned().collect()
+    }
+
+    /// Walks `devanagari` one akshara at a time: a consonant cluster chained
+    /// by explicit viramas (a written conjunct), followed by its vowel - a
+    /// matra if present, otherwise the implicit schwa. Anusvara/visarga
+    /// following the vowel are folded into the nucleus (nasalization, or a
+    /// trailing aspiration respectively). Characters this table doesn't
+    /// recognize (punctuation, digits, stray marks) pass through as their
+    /// own zero-onset "syllable" so `syllabify_ipa` doesn't swallow them.
+    fn ipa_aksharas(devanagari: &str) -> Vec<(Vec<&'static str>, String)> {
+        const HALANTA: char = '\u{094d}';
+        const ANUSVARA: char = '\u{0902}';
+        const VISARGA: char = '\u{0903}';
+
+        let consonants = Self::ipa_consonant_table();
+        let vowels_full = Self::ipa_vowel_full_table();
+        let matras = Self::ipa_matra_table();
+
+        let chars: Vec<char> = devanagari.chars().collect();
+        let mut aksharas = Vec::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let current = chars[i].to_string();
+
+            if let Some(&ipa) = consonants.get(current.as_str()) {
+                let mut onset = vec![ipa];
+                i += 1;
+                while chars.get(i) == Some(&HALANTA) {
+                    let Some(next_char) = chars.get(i + 1) else { break };
+                    let Some(&next_ipa) = consonants.get(next_char.to_string().as_str()) else { break };
+                    onset.push(next_ipa);
+                    i += 2;
+                }
+
+                let mut nucleus = match chars.get(i).map(|c| c.to_string()) {
+                    Some(matra) if matras.contains_key(matra.as_str()) => {
+                        i += 1;
+                        matras[matra.as_str()].to_string()
+                    }
+                    // No matra (and no halanta, which would have been
+                    // consumed above): the implicit schwa. `syllabify_ipa`
+                    // downgrades this to the superscript ᵊ if word-final.
+                    _ => "ə".to_string(),
+                };
+
+                match chars.get(i) {
+                    Some(&ANUSVARA) => { nucleus.push('\u{303}'); i += 1; }
+                    Some(&VISARGA) => { nucleus.push('ʰ'); i += 1; }
+                    _ => {}
+                }
+
+                aksharas.push((onset, nucleus));
+            } else if let Some(&ipa) = vowels_full.get(current.as_str()) {
+                i += 1;
+                let mut nucleus = ipa.to_string();
+                match chars.get(i) {
+                    Some(&ANUSVARA) => { nucleus.push('\u{303}'); i += 1; }
+                    Some(&VISARGA) => { nucleus.push('ʰ'); i += 1; }
+                    _ => {}
+                }
+                aksharas.push((Vec::new(), nucleus));
+            } else {
+                i += 1;
+                aksharas.push((Vec::new(), current));
+            }
+        }
+
+        aksharas
+    }
+
+    /// Joins aksharas into dotted-syllable IPA. A legal onset here is at
+    /// most two consonants; any earlier members of a longer written
+    /// conjunct become the coda of the *previous* syllable instead, which is
+    /// what actually splits a multi-consonant conjunct across a syllable
+    /// boundary (शास्त्र's written स्त्र comes out s.t̪ɾ, not a 3-consonant
+    /// onset). The word-final akshara's implicit schwa is downgraded to the
+    /// superscript ᵊ, and a bare final i/u is lengthened, matching the
+    /// reference transcriptions' treatment of word-final vowels.
+    fn syllabify_ipa(mut aksharas: Vec<(Vec<&'static str>, String)>) -> String {
+        if let Some((_, nucleus)) = aksharas.last_mut() {
+            match nucleus.as_str() {
+                "ə" => *nucleus = "ᵊ".to_string(),
+                "i" => *nucleus = "iː".to_string(),
+                "u" => *nucleus = "uː".to_string(),
+                _ => {}
+            }
+        }
+
+        const MAX_ONSET: usize = 2;
+        let mut syllables: Vec<String> = Vec::with_capacity(aksharas.len());
+
+        for (onset, nucleus) in aksharas {
+            let overflow = onset.len().saturating_sub(MAX_ONSET);
+            let (carry_to_prev, kept_onset) = if overflow > 0 && !syllables.is_empty() {
+                onset.split_at(overflow)
+            } else {
+                (&[][..], &onset[..])
+            };
+
+            if let Some(prev) = syllables.last_mut() {
+                prev.push_str(&carry_to_prev.concat());
+            }
+            syllables.push(format!("{}{}", kept_onset.concat(), nucleus));
+        }
+
+        syllables.join(".")
+    }
+
+    /// Broad IPA transcription with syllable boundaries, modeled on the
+    /// Nepali/Hindi IPA Wiktionary modules. Reuses the FST to get the
+    /// Devanagari spelling, then maps each grapheme to its phoneme and
+    /// syllabifies (see `ipa_aksharas`/`syllabify_ipa`). This is a broad,
+    /// dictionary-pronunciation transcription, not a phonetic transcription
+    /// of any one speaker's actual realization.
+    pub fn transliterate_to_ipa(&self, roman: &str) -> String {
+        if roman.is_empty() {
+            return String::new();
+        }
+        let devanagari = self.transliterate_primary(roman);
+        Self::syllabify_ipa(Self::ipa_aksharas(&devanagari))
+    }
+
+    /// Ergonomic-scheme vowel tokens that are also a valid bare-vowel +
+    /// consonant split ("am" = anusvara, but also "a" + "m"; "ah"/"aH" =
+    /// visarga, but also "a" + "h"/"H"; "An" = anusvara, but also "A" +
+    /// "n"). These never win the longest-match race: favoring the longer
+    /// alias silently mangled any ordinary word containing one of these
+    /// letter runs mid-word (e.g. "namaste"'s "am", or a case-folded
+    /// all-caps token routed through `transliterate_alternate`), not just
+    /// the medial-schwa skeletons (`expand_medial_schwa`) this was
+    /// originally scoped to. The single-letter forms ("M", "H", etc.)
+    /// still type the anusvara/visarga directly.
+    const AMBIGUOUS_VOWEL_ALIASES: [&'static str; 4] = ["am", "An", "ah", "aH"];
+
+    /// Implements Longest Prefix Match (LPM) and categorizes the match,
+    /// skipping `AMBIGUOUS_VOWEL_ALIASES` so the shorter, unambiguous split
+    /// is matched instead.
     fn match_longest<'a>(&'a self, slice: &'a str) -> Option<(&'a str, MatchResult<'a>, MapKind)> {
         for len in (1..=slice.len()).rev() {
             let token = &slice[0..len];
-            
-            if let Some(val) = self.symbols.get(token) { 
-                return Some((token, MatchResult::Symbol(*val), MapKind::Symbol)); 
+
+            if let Some(val) = self.symbols.get(token) {
+                return Some((token, MatchResult::Symbol(*val), MapKind::Symbol));
+            }
+            if let Some(val) = self.consonants.get(token) {
+                return Some((token, MatchResult::Consonant(*val), MapKind::Consonant));
             }
-            if let Some(val) = self.consonants.get(token) { 
-                return Some((token, MatchResult::Consonant(*val), MapKind::Consonant)); 
+            if Self::AMBIGUOUS_VOWEL_ALIASES.contains(&token) {
+                continue;
             }
             if let Some((full, matra)) = self.vowels.get(token) {
                 return Some((token, MatchResult::Vowel { full, matra }, MapKind::Vowel));
@@ -389,4 +1000,72 @@ impl RomanizationEngine {
     }
 }
 
-impl Default for RomanizationEngine { fn default() -> Self { Self::new() } }
\ No newline at end of file
+impl Default for RomanizationEngine { fn default() -> Self { Self::new() } }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slp1_word_final_visarga_attaches_to_implicit_schwa() {
+        let engine = RomanizationEngine::with_scheme(Scheme::Slp1);
+        let devanagari = engine.transliterate_primary("rAmaH");
+        assert_eq!(devanagari, "रामः");
+        assert_eq!(engine.detransliterate(&devanagari), "rAmaH");
+    }
+
+    #[test]
+    fn transliterate_natural_expands_bare_consonant_runs() {
+        let engine = RomanizationEngine::new();
+        assert_eq!(engine.transliterate_natural("kml"), "कमल");
+    }
+
+    #[test]
+    fn transliterate_natural_does_not_misread_am_as_anusvara() {
+        let engine = RomanizationEngine::new();
+        // "namskar" isn't recovered with its colloquial long vowel (a known
+        // limitation - see `transliterate_natural`'s doc comment), but the
+        // schwa expansion must not hijack the medial "am" into an anusvara.
+        assert_eq!(engine.transliterate_natural("namskar"), "नमस्कर");
+    }
+
+    #[test]
+    fn transliterate_primary_does_not_misread_am_as_anusvara() {
+        let engine = RomanizationEngine::new();
+        // "namaste" contains a literal "am" - `match_longest` must not take
+        // that as the anusvara alias outside the medial-schwa pass either.
+        assert_eq!(engine.transliterate_primary("namaste"), "नमस्ते");
+    }
+
+    #[test]
+    fn transliterate_alternate_keeps_the_trailing_halanta() {
+        let engine = RomanizationEngine::new();
+        assert_eq!(engine.transliterate_primary("kam"), "कम");
+        assert_eq!(engine.transliterate_alternate("kam"), "कम्");
+    }
+
+    #[test]
+    fn transliterate_to_ipa_matches_the_worked_examples() {
+        let engine = RomanizationEngine::new();
+        assert_eq!(engine.transliterate_to_ipa("madhu"), "mə.d̪ʱuː");
+        assert_eq!(engine.transliterate_to_ipa("shaastra"), "ʃɑːs.t̪ɾᵊ");
+    }
+
+    #[test]
+    fn lexicon_reorders_generate_candidates_output() {
+        let engine = RomanizationEngine::new();
+        let candidates = engine.generate_candidates("kam");
+        // Sanity check: the medial-vowel-promotion heuristic offers "काम" as
+        // an unranked alternative behind the primary "कम" before any lexicon
+        // is attached.
+        assert_eq!(candidates.first().map(String::as_str), Some("कम"));
+        assert!(candidates.iter().any(|c| c == "काम"));
+
+        let mut lexicon = HashMap::new();
+        lexicon.insert("काम".to_string(), 100u32);
+        let engine = engine.with_lexicon(lexicon);
+
+        let candidates = engine.generate_candidates("kam");
+        assert_eq!(candidates.first().map(String::as_str), Some("काम"));
+    }
+}
\ No newline at end of file
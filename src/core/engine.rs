@@ -1,12 +1,21 @@
 // File: src/core/engine.rs
 use crate::core::{
+    config::{self, SuggestionConfig},
     context::ContextModel, converter::RomanizationEngine,
-    trie::TrieBuilder, types::WordId,
+    ranking::{self, Candidate, Criterion, RankingContext, ScoreDetails, ScoredSuggestion, ScoringWeights},
+    synonyms::SynonymTable,
+    trie::TrieBuilder,
+    types::WordId,
 };
+use crate::fuzzy::levenshtein_automaton::LevenshteinAutomatonBuilder;
+use crate::fuzzy::subsequence::SubsequenceMatcher;
 use crate::fuzzy::symspell::SymSpell;
 use crate::learning::{LearningEngine, WordConfirmation};
-use crate::persistence::{load_from_disk, save_to_disk};
+use crate::persistence::{
+    self, load_from_disk, load_seed_dictionary, save_to_disk, DictionaryFormat, BUNDLED_SEED_DICTIONARY,
+};
 use std::collections::HashMap;
+use std::io::BufRead;
 use std::path::Path;
 
 const CONTEXT_WINDOW_SIZE: usize = 3;
@@ -17,14 +26,36 @@ const LITERAL_BASE_SCORE: u64 = 1;
 // so it appears before other generated variants if no dictionary entry exists.
 const PRIMARY_LITERAL_SCORE: u64 = 2;
 
-/// Defines the origin of a suggestion to allow for intelligent ranking.
-/// Higher variants are considered higher quality.
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+/// Defines the origin of a suggestion. Used only to look up that stage's
+/// `source_prior` weight - it no longer hard-orders candidates the way it
+/// used to; a strong fuzzy or context match can now outrank a weak trie hit,
+/// since `get_suggestions_explained` settles duplicates by weighted score
+/// instead of by stage alone.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 enum SuggestionSource {
     Literal,        // A heuristic-based variant from the FST
     PrimaryLiteral, // The single, deterministic FST output
-    Fuzzy,          // A match from SymSpell
-    Trie,           // A direct prefix match from the learned dictionary (highest quality)
+    Fuzzy,          // A match from SymSpell or the Levenshtein automaton
+    Subsequence,    // A skip-letter sketch match from the subsequence matcher
+    Trie,           // A direct prefix match from the learned dictionary
+}
+
+impl SuggestionSource {
+    /// A prior belief in this stage's reliability, fed into
+    /// `ranking::score_candidate` alongside context/frequency/edit-distance
+    /// rather than used to override them outright.
+    fn prior(self) -> f32 {
+        match self {
+            SuggestionSource::Trie => 3.0,
+            SuggestionSource::Fuzzy => 2.0,
+            SuggestionSource::PrimaryLiteral => 1.5,
+            SuggestionSource::Literal => 1.0,
+            // A sparse sketch is a weaker signal than an adjacent-character
+            // typo, so it sits below Fuzzy: it's meant to surface a learned
+            // word a tighter edit-distance search missed, not outrank one.
+            SuggestionSource::Subsequence => 0.5,
+        }
+    }
 }
 
 pub struct ImeEngine {
@@ -32,6 +63,37 @@ pub struct ImeEngine {
     pub context_model: ContextModel,
     pub romanizer: RomanizationEngine,
     pub symspell: SymSpell,
+    /// Char-bag-prefiltered subsequence index over learned words only, so a
+    /// sparse, skip-letter sketch can still recall a word the user has
+    /// specifically taught the IME. See `SubsequenceMatcher`.
+    pub subsequence: SubsequenceMatcher,
+    /// User- or config-declared alias groups that collapse several
+    /// romanizations/spellings onto one canonical word. See `add_synonym`.
+    pub synonyms: SynonymTable,
+    /// Tiebreaker chain `get_suggestions_explained` runs survivors through
+    /// before the weighted-score sort, so candidates with an equal total
+    /// still land in a deterministic order. Downstream integrators can
+    /// reorder, drop, or append criteria here instead of editing
+    /// `get_suggestions_explained` itself.
+    pub criteria: Vec<Box<dyn Criterion>>,
+    /// Tunable beam/weight/n-best knobs for `get_suggestions`. See `SuggestionConfig`.
+    pub suggestion_config: SuggestionConfig,
+    /// Weights `get_suggestions_explained` combines each candidate's
+    /// sub-scores with. See `ranking::ScoringWeights`.
+    pub scoring_weights: ScoringWeights,
+    /// Whether `transliterate_phrase` treats an ALL-CAPS roman token (e.g.
+    /// "RAMA") as a request for its alternate, schwa-preserving spelling
+    /// (`RomanizationEngine::transliterate_alternate`) instead of the
+    /// normal case-folded dictionary suggestion. Off by default - it's a
+    /// deliberate escape hatch, not something most callers expect
+    /// capitalization to trigger. Devanagari itself carries no case, so
+    /// every roman token is case-folded for matching either way.
+    pub all_caps_alternate: bool,
+    /// One reusable `LevenshteinAutomatonBuilder` per edit-distance budget
+    /// (0, 1, 2), indexed by distance. Built once so `get_suggestions` never
+    /// re-derives the (cheap but repeated) max-distance setting per query.
+    /// See `automaton_budget` for how a query picks its index.
+    automaton_builders: [LevenshteinAutomatonBuilder; 3],
     learning_engine: LearningEngine,
     dictionary_path: Option<String>,
 }
@@ -43,53 +105,222 @@ impl ImeEngine {
             context_model: ContextModel::new(CONTEXT_WINDOW_SIZE),
             romanizer: RomanizationEngine::new(),
             symspell: SymSpell::new(MAX_EDIT_DISTANCE),
+            subsequence: SubsequenceMatcher::new(),
+            synonyms: SynonymTable::new(),
+            criteria: ranking::default_criteria(),
+            suggestion_config: SuggestionConfig::new(),
+            scoring_weights: ScoringWeights::new(),
+            all_caps_alternate: false,
+            automaton_builders: [
+                LevenshteinAutomatonBuilder::new(0),
+                LevenshteinAutomatonBuilder::new(1),
+                LevenshteinAutomatonBuilder::new(2),
+            ],
             learning_engine: LearningEngine::new(),
             dictionary_path: None,
         }
     }
 
+    /// Picks an edit-distance budget from the preedit's length for the
+    /// Levenshtein-automaton search: very short prefixes (<=2 chars) only
+    /// tolerate exact matches, since a typo budget of 1-2 edits would
+    /// otherwise make almost anything in the trie a "fuzzy" match.
+    fn automaton_budget(prefix_char_count: usize) -> usize {
+        match prefix_char_count {
+            0..=2 => 0,
+            3..=5 => 1,
+            _ => 2,
+        }
+    }
+
     pub fn from_file_or_new(path: &str) -> Self {
-        let mut engine = load_from_disk(Path::new(path)).unwrap_or_else(|_| Self::new());
+        let mut engine = load_from_disk(Path::new(path)).unwrap_or_else(|_| {
+            let mut seeded = Self::new();
+            seeded.load_seed_dictionary(BUNDLED_SEED_DICTIONARY.as_bytes());
+            seeded
+        });
         engine.dictionary_path = Some(path.to_string());
+        engine.sync_lexicon();
         engine
     }
 
-    pub fn get_suggestions(&self, prefix: &str, count: usize) -> Vec<(String, u64)> {
+    /// Bulk-populates the trie and fuzzy index from a plain-text dictionary of
+    /// `devanagari<TAB>roman<TAB>frequency` lines. See `persistence::load_seed_dictionary`
+    /// for the expected format and the sorted-input fast path.
+    pub fn load_seed_dictionary(&mut self, reader: impl BufRead) {
+        if let Err(e) = load_seed_dictionary(&mut self.trie_builder, &mut self.symspell, reader) {
+            eprintln!("[Rust ERR] Failed to load seed dictionary: {}", e);
+        }
+        self.sync_lexicon();
+    }
+
+    /// Bulk-imports an external frequency list or Hunspell-style word list
+    /// (see `DictionaryFormat`), so a fresh install can ship with a base
+    /// lexicon harvested from existing Nepali spelling resources instead of
+    /// requiring every user to re-teach common vocabulary through
+    /// `user_confirms`. Returns the number of words imported.
+    pub fn import_dictionary(
+        &mut self,
+        reader: impl BufRead,
+        format: DictionaryFormat,
+        seed_context: bool,
+    ) -> Result<usize, std::io::Error> {
+        let imported = persistence::import_dictionary(
+            &mut self.trie_builder,
+            &mut self.symspell,
+            &mut self.context_model,
+            reader,
+            format,
+            seed_context,
+        );
+        self.sync_lexicon();
+        imported
+    }
+
+    /// Rebuilds `self.romanizer`'s lexicon (Devanagari form -> frequency)
+    /// from the current trie contents, so `generate_candidates` re-ranks by
+    /// this engine's actual dictionary instead of staying in fixed
+    /// heuristic-insertion order. Called after bulk loads
+    /// (`from_file_or_new`, `load_seed_dictionary`, `import_dictionary`);
+    /// `user_confirms` and `add_synonym` don't call it, so words or
+    /// frequencies they add only reach the lexicon at the next bulk load.
+    fn sync_lexicon(&mut self) {
+        let lexicon = self
+            .trie_builder
+            .metadata_store
+            .iter()
+            .map(|metadata| (metadata.nepali.clone(), u32::try_from(metadata.frequency).unwrap_or(u32::MAX)))
+            .collect();
+        self.romanizer.set_lexicon(lexicon);
+    }
+
+    /// Declares `alias` (a Roman or Devanagari spelling) as a synonym of
+    /// `canonical`, so typing the alias surfaces the canonical word's combined
+    /// frequency and suggestions instead of building up a separate entry.
+    /// Returns the canonical word's `WordId`.
+    pub fn add_synonym(&mut self, alias: &str, canonical: &str) -> WordId {
+        let canonical_id = self.trie_builder.get_or_create_metadata(canonical);
+        self.synonyms.add(alias, canonical_id);
+        self.symspell.add_word(alias, canonical_id);
+        canonical_id
+    }
+
+    /// Gathers and scores suggestions for `prefix`, returning the full
+    /// `ScoreDetails` breakdown behind each one's rank - context probability,
+    /// log-frequency, edit-distance penalty, and source prior, each already
+    /// weighted by `self.scoring_weights`. `get_suggestions` is a thin
+    /// wrapper over this that keeps the simpler `(String, u64)` shape older
+    /// callers expect.
+    pub fn get_suggestions_explained(&self, prefix: &str, count: usize) -> Vec<ScoredSuggestion> {
         if prefix.is_empty() { return vec![]; }
 
-        let mut candidates: HashMap<String, (u64, SuggestionSource)> = HashMap::new();
-
-        // Helper to add candidates while respecting suggestion source quality.
-        // A higher-quality source (e.g., Trie) will always overwrite a lower one (e.g., Literal),
-        // regardless of score.
-        let mut add_candidate = |nepali: String, score: u64, source: SuggestionSource| {
-            candidates.entry(nepali)
-                .and_modify(|(existing_score, existing_source)| {
-                    if source > *existing_source {
-                        *existing_score = score;
-                        *existing_source = source;
-                    } else if source == *existing_source && score > *existing_score {
-                        *existing_score = score;
+        let ranking_ctx = RankingContext { preedit: prefix, context_model: &self.context_model };
+
+        let mut candidates: HashMap<String, (Candidate, ScoreDetails)> = HashMap::new();
+
+        // Helper to add a candidate, scoring it against `ranking_ctx` with the
+        // engine's `scoring_weights`. Duplicate Nepali strings from different
+        // stages (e.g. a trie hit and a fuzzy hit for the same word) are
+        // settled by comparing their weighted `total()`, not by which stage
+        // produced them - a strong fuzzy/context match can now outrank a
+        // weak trie hit instead of always losing to it.
+        let mut add_candidate = |candidate: Candidate, source: SuggestionSource| {
+            let details = ranking::score_candidate(&candidate, source.prior(), &ranking_ctx, &self.scoring_weights);
+            candidates.entry(candidate.nepali.clone())
+                .and_modify(|(existing, existing_details)| {
+                    if details.total() > existing_details.total() {
+                        *existing = candidate.clone();
+                        *existing_details = details;
                     }
                 })
-                .or_insert((score, source));
+                .or_insert((candidate, details));
         };
 
+        // --- Stage 0: Synonym Resolution ---
+        // If the preedit is a declared alias, fold it into the canonical word's
+        // candidate so a rarely-typed spelling inherits the canonical form's
+        // combined frequency and devanagari rendering.
+        if let Some(canonical_id) = self.synonyms.resolve(prefix) {
+            if let Some(metadata) = self.trie_builder.metadata_store.get(canonical_id) {
+                add_candidate(
+                    Candidate { word_id: Some(canonical_id), nepali: metadata.nepali.clone(), score: metadata.frequency, edit_distance: Some(0) },
+                    SuggestionSource::Trie,
+                );
+            }
+        }
+
         // --- Stage 1: Trie Search (Highest Quality) ---
         let trie_suggestions = self.trie_builder.get_top_k_suggestions(prefix, count);
         for (word_id, score) in trie_suggestions {
             if let Some(metadata) = self.trie_builder.metadata_store.get(word_id) {
-                add_candidate(metadata.nepali.clone(), score, SuggestionSource::Trie);
+                add_candidate(
+                    Candidate { word_id: Some(word_id), nepali: metadata.nepali.clone(), score, edit_distance: Some(0) },
+                    SuggestionSource::Trie,
+                );
             }
         }
 
         // --- Stage 2: Fuzzy Search ---
-        let fuzzy_matches = self.symspell.lookup(prefix);
-        for word_id in fuzzy_matches {
+        // `lookup` now returns true edit distances, ranked ascending; fold that into
+        // the existing frequency-based score so exact (distance-0) matches are never
+        // penalized and worse typos are penalized proportionally more.
+        let fuzzy_matches = self.symspell.lookup_bounded(prefix, self.suggestion_config.node_pool_size);
+        for (word_id, distance) in fuzzy_matches {
             if let Some(metadata) = self.trie_builder.metadata_store.get(word_id) {
-                // Fuzzy matches are penalized slightly to rank below exact prefix matches.
-                let score = metadata.frequency.saturating_sub(1);
-                add_candidate(metadata.nepali.clone(), score, SuggestionSource::Fuzzy);
+                let score = if distance == 0 {
+                    metadata.frequency
+                } else {
+                    metadata.frequency.saturating_sub(distance as u64)
+                };
+                add_candidate(
+                    Candidate { word_id: Some(word_id), nepali: metadata.nepali.clone(), score, edit_distance: Some(distance) },
+                    SuggestionSource::Fuzzy,
+                );
+            }
+        }
+
+        // --- Stage 2b: Levenshtein-Automaton Fuzzy Search ---
+        // Complements Stage 2 with a search whose cost scales with the edit-
+        // distance budget rather than the dictionary size: the trie and the
+        // automaton are walked together, pruning a whole subtree the moment
+        // the DP row proves no descendant can stay within budget. Candidates
+        // overlapping Stage 2's SymSpell hits are deduplicated by `add_candidate`.
+        let budget = Self::automaton_budget(prefix.chars().count());
+        let query_chars: Vec<char> = prefix.chars().collect();
+        let automaton = self.automaton_builders[budget].build(&query_chars);
+        let automaton_matches = self.trie_builder.fuzzy_search(&automaton, &automaton.start());
+        for (word_id, distance) in automaton_matches {
+            if let Some(metadata) = self.trie_builder.metadata_store.get(word_id) {
+                let score = if distance == 0 {
+                    metadata.frequency
+                } else {
+                    metadata.frequency.saturating_sub(distance as u64)
+                };
+                add_candidate(
+                    Candidate { word_id: Some(word_id), nepali: metadata.nepali.clone(), score, edit_distance: Some(distance) },
+                    SuggestionSource::Fuzzy,
+                );
+            }
+        }
+
+        // --- Stage 2c: Subsequence Fuzzy Search (Learned Words) ---
+        // Recalls a learned word from a sparse, skip-letter sketch of it
+        // (e.g. "nmst" for "namaste") that neither SymSpell nor the
+        // automaton would reach, since both bound how many *adjacent*
+        // characters can be missing/extra/transposed rather than how many
+        // can be skipped anywhere in the word.
+        let subsequence_matches = self.subsequence.lookup(prefix);
+        for word_id in subsequence_matches {
+            if let Some(metadata) = self.trie_builder.metadata_store.get(word_id) {
+                add_candidate(
+                    Candidate {
+                        word_id: Some(word_id),
+                        nepali: metadata.nepali.clone(),
+                        score: metadata.frequency,
+                        edit_distance: None,
+                    },
+                    SuggestionSource::Subsequence,
+                );
             }
         }
 
@@ -97,46 +328,64 @@ impl ImeEngine {
         // This is the single, most direct transliteration from the FST. We add it with a
         // special priority to ensure it's always an option for the user.
         let primary_nepali = self.romanizer.transliterate_primary(prefix);
-        add_candidate(primary_nepali, PRIMARY_LITERAL_SCORE, SuggestionSource::PrimaryLiteral);
+        let primary_word_id = self.trie_builder.find_word_id_by_nepali(&primary_nepali);
+        add_candidate(
+            Candidate { word_id: primary_word_id, nepali: primary_nepali, score: PRIMARY_LITERAL_SCORE, edit_distance: None },
+            SuggestionSource::PrimaryLiteral,
+        );
 
         // --- Stage 4: Other Literal FSM Candidates (Fallback Heuristics) ---
         let literal_candidates = self.romanizer.generate_candidates(prefix);
         for nepali in literal_candidates {
             // This will only insert if the candidate isn't already present from a better source.
-            add_candidate(nepali, LITERAL_BASE_SCORE, SuggestionSource::Literal);
+            let word_id = self.trie_builder.find_word_id_by_nepali(&nepali);
+            add_candidate(
+                Candidate { word_id, nepali, score: LITERAL_BASE_SCORE, edit_distance: None },
+                SuggestionSource::Literal,
+            );
         }
 
-        // --- Stage 5: Conversion, Contextual Re-ranking, and Final Sort ---
-        let mut all_suggestions: Vec<(String, u64)> = candidates
-            .into_iter()
-            .map(|(s, (score, _))| (s, score))
-            .collect();
+        // --- Stage 5: Beam/Weight Pruning, then Weighted Ranking ---
+        // `config::prune` trims by `Candidate::cost()` (an absolute/relative
+        // edit-distance-and-frequency budget, independent of scoring_weights)
+        // before the scored candidates are sorted.
+        let mut survivors: Vec<Candidate> = candidates.values().map(|(candidate, _)| candidate.clone()).collect();
+        config::prune(&mut survivors, &self.suggestion_config);
 
-        // Contextual re-ranking
-        let mut suggestions_with_ids: Vec<(WordId, u64)> = all_suggestions.iter()
-            .filter_map(|(nepali, score)| {
-                self.trie_builder.find_word_id_by_nepali(nepali).map(|id| (id, *score))
-            })
-            .collect();
+        // `self.criteria`'s stable lexicographic chain still runs first, so its
+        // relative order survives as the tiebreaker for any candidates whose
+        // weighted totals land exactly equal; the weighted total is then the
+        // dominant key via the second, stable sort below.
+        ranking::apply_criteria(&self.criteria, &ranking_ctx, &mut survivors);
 
-        self.context_model.rerank_suggestions(&mut suggestions_with_ids);
+        let mut scored: Vec<ScoredSuggestion> = survivors
+            .into_iter()
+            .filter_map(|c| candidates.get(&c.nepali).map(|(_, details)| ScoredSuggestion { nepali: c.nepali, score: *details }))
+            .collect();
+        scored.sort_by(|a, b| b.score.total().partial_cmp(&a.score.total()).unwrap_or(std::cmp::Ordering::Equal));
 
-        for (id, new_score) in suggestions_with_ids {
-            let nepali_word = &self.trie_builder.metadata_store[id].nepali;
-            if let Some(entry) = all_suggestions.iter_mut().find(|(s, _)| s == nepali_word) {
-                entry.1 = new_score;
-            }
-        }
+        let limit = match self.suggestion_config.n_best {
+            Some(n_best) => count.min(n_best),
+            None => count,
+        };
+        scored.truncate(limit);
+        scored
+    }
 
-        all_suggestions.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
-        all_suggestions.truncate(count);
-        all_suggestions
+    /// Thin wrapper over `get_suggestions_explained` for callers that only
+    /// want the surface form and a simple ranking score, not the full
+    /// breakdown (e.g. the C FFI layer).
+    pub fn get_suggestions(&self, prefix: &str, count: usize) -> Vec<(String, u64)> {
+        self.get_suggestions_explained(prefix, count)
+            .into_iter()
+            .map(|s| (s.nepali, s.score.total().max(0.0) as u64))
+            .collect()
     }
 
     pub fn user_confirms(&mut self, roman: &str, nepali: &str) {
         if roman.is_empty() || nepali.is_empty() { return; }
         let confirmation = WordConfirmation { roman: roman.to_string(), nepali: nepali.to_string() };
-        self.learning_engine.learn(&mut self.trie_builder, &mut self.context_model, &mut self.symspell, &confirmation);
+        self.learning_engine.learn(&mut self.trie_builder, &mut self.context_model, &mut self.symspell, &mut self.subsequence, &self.synonyms, &confirmation);
     }
 
     pub fn save_dictionary(&self) -> Result<(), std::io::Error> {
@@ -148,6 +397,139 @@ impl ImeEngine {
         }
     }
 
+    /// Transliterates a whole phrase instead of a single preedit word:
+    /// `phrase` is tokenized into runs of Roman letters and everything else
+    /// (whitespace, punctuation, digits, already-Devanagari text), each
+    /// Roman run is replaced by its best suggestion (falling back to the
+    /// primary FST guess if the dictionary has nothing for it), and every
+    /// other run - including any Devanagari the caller had already mixed in,
+    /// e.g. pasted text - passes through untouched.
+    ///
+    /// Chosen words are threaded through `self.context_model` as the phrase
+    /// is walked, so later words in the same phrase get the bigram boost
+    /// earlier ones provide, then the context is restored to how it stood
+    /// before the call: scoring a phrase isn't the same as the user
+    /// confirming it (see `user_confirms`), so the build-up is intra-phrase
+    /// only and never persisted.
+    ///
+    /// Every roman token is case-folded before matching, since Devanagari
+    /// itself carries no case; see `all_caps_alternate` for the one
+    /// exception (an ALL-CAPS token mapping to an alternate spelling).
+    pub fn transliterate_phrase(&mut self, phrase: &str) -> String {
+        let original_context = self.context_model.clone();
+        let mut result = String::with_capacity(phrase.len() * 2);
+
+        for token in tokenize_phrase(phrase) {
+            match token {
+                PhraseToken::Passthrough(text) => result.push_str(text),
+                PhraseToken::Word(word) => result.push_str(&self.transliterate_word_in_context(word)),
+            }
+        }
+
+        self.context_model = original_context;
+        result
+    }
+
+    /// Picks `word`'s best suggestion (or the primary FST guess if none
+    /// exists), feeds it into `self.context_model` so the next call in the
+    /// same phrase sees it as the preceding word, and returns it.
+    ///
+    /// Devanagari carries no case of its own, so `word` is case-folded
+    /// before matching either way. If `self.all_caps_alternate` is set and
+    /// `word` is itself ALL-CAPS (e.g. "RAMA"), that's instead read as a
+    /// request for the alternate, schwa-preserving spelling rather than the
+    /// usual case-folded dictionary suggestion.
+    fn transliterate_word_in_context(&mut self, word: &str) -> String {
+        let folded = word.to_lowercase();
+
+        if self.all_caps_alternate && is_all_caps_word(word) {
+            return self.romanizer.transliterate_alternate(&folded);
+        }
+
+        let nepali = self
+            .get_suggestions(&folded, 1)
+            .into_iter()
+            .next()
+            .map(|(nepali, _)| nepali)
+            .unwrap_or_else(|| self.romanizer.transliterate_primary(&folded));
+
+        if let Some(word_id) = self.trie_builder.find_word_id_by_nepali(&nepali) {
+            self.context_model.add_word(word_id);
+        }
+        nepali
+    }
+
 }
 
-impl Default for ImeEngine { fn default() -> Self { Self::new() } }
\ No newline at end of file
+/// One maximal run of phrase text, as split by `tokenize_phrase`.
+enum PhraseToken<'a> {
+    /// A run of Roman letters to transliterate.
+    Word(&'a str),
+    /// A run of anything else - whitespace, punctuation, digits, or
+    /// already-Devanagari text - to carry through unchanged.
+    Passthrough(&'a str),
+}
+
+/// Splits `phrase` into maximal runs of ASCII letters and maximal runs of
+/// everything else, in order, so `transliterate_phrase` can transliterate the
+/// former and pass the latter through verbatim (including exact whitespace
+/// and any non-Roman script already present).
+fn tokenize_phrase(phrase: &str) -> Vec<PhraseToken> {
+    let mut tokens = Vec::new();
+    let mut rest = phrase;
+
+    while !rest.is_empty() {
+        let starts_word = rest.chars().next().is_some_and(|c| c.is_ascii_alphabetic());
+        let split_at = rest
+            .find(|c: char| c.is_ascii_alphabetic() != starts_word)
+            .unwrap_or(rest.len());
+        let (chunk, remainder) = rest.split_at(split_at);
+
+        tokens.push(if starts_word { PhraseToken::Word(chunk) } else { PhraseToken::Passthrough(chunk) });
+        rest = remainder;
+    }
+
+    tokens
+}
+
+/// True for a roman word of more than one letter that's entirely uppercase
+/// (e.g. "RAMA"), as opposed to a single capitalized letter or mixed case -
+/// the signal `transliterate_word_in_context` looks for when
+/// `ImeEngine::all_caps_alternate` is enabled.
+fn is_all_caps_word(word: &str) -> bool {
+    word.chars().count() > 1 && word.chars().all(|c| c.is_ascii_uppercase())
+}
+
+impl Default for ImeEngine { fn default() -> Self { Self::new() } }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transliterate_phrase_case_folds_roman_tokens() {
+        let mut engine = ImeEngine::new();
+        assert_eq!(engine.transliterate_phrase("RAmaste"), engine.transliterate_phrase("ramaste"));
+    }
+
+    #[test]
+    fn loading_a_dictionary_feeds_the_romanizer_lexicon() {
+        let mut engine = ImeEngine::new();
+        // "काम" isn't transliterate_primary("kam")'s output ("कम") - it's the
+        // medial-vowel-promotion heuristic's alternate reading - so seeding
+        // it with a high frequency is the only thing that can put it first.
+        engine.load_seed_dictionary("काम\tkam\t100\n".as_bytes());
+
+        let candidates = engine.romanizer.generate_candidates("kam");
+        assert_eq!(candidates.first().map(String::as_str), Some("काम"));
+    }
+
+    #[test]
+    fn all_caps_alternate_keeps_the_trailing_halanta() {
+        let mut engine = ImeEngine::new();
+        assert_eq!(engine.transliterate_phrase("KAM"), "कम");
+
+        engine.all_caps_alternate = true;
+        assert_eq!(engine.transliterate_phrase("KAM"), "कम्");
+    }
+}
\ No newline at end of file
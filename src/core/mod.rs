@@ -0,0 +1,20 @@
+// File: src/core/mod.rs
+//
+// `context`, `trie`, and `types` make up the no_std-compatible ranking core
+// (see crate root docs). Everything else here builds on top of `ImeEngine`
+// and needs `std` (file I/O via `persistence`, owned trait objects sized for
+// a desktop integration, etc.), so it's gated behind the `std` feature.
+pub mod context;
+pub mod trie;
+pub mod types;
+
+#[cfg(feature = "std")]
+pub mod config;
+#[cfg(feature = "std")]
+pub mod converter;
+#[cfg(feature = "std")]
+pub mod engine;
+#[cfg(feature = "std")]
+pub mod ranking;
+#[cfg(feature = "std")]
+pub mod synonyms;
@@ -0,0 +1,235 @@
+// File: src/core/ranking.rs
+//
+// Suggestion ranking as a chain of criteria, the way a search engine's
+// `SortField` stack works: each criterion refines or breaks the ties left by
+// the one before it. This replaces the old two-entangled-functions approach
+// (`TrieBuilder::get_top_k_suggestions` sorting by frequency, `ContextModel`
+// bolting a bigram boost on top) with a single, testable, reorderable pipeline.
+use crate::core::context::ContextModel;
+use crate::core::types::WordId;
+use serde::Serialize;
+
+/// A single suggestion as it flows through the ranking pipeline.
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    /// `None` for literal FST guesses that aren't (yet) in the learned dictionary.
+    pub word_id: Option<WordId>,
+    pub nepali: String,
+    /// Raw frequency-derived score from the gathering stage (trie/fuzzy/literal).
+    pub score: u64,
+    /// True edit distance from the fuzzy stage; `Some(0)` for an exact trie or
+    /// synonym hit, `None` for a candidate with no edit-distance measure (a
+    /// literal FST guess or a subsequence match).
+    pub edit_distance: Option<usize>,
+}
+
+impl Candidate {
+    /// Converts this candidate's score into a cost where *lower is better*,
+    /// for `SuggestionConfig`'s beam/weight pruning: each edit distance point
+    /// is a flat penalty, and frequency is a log-scaled discount (diminishing
+    /// returns, consistent with `ContextModel::bigram_boost`'s use of log2).
+    pub fn cost(&self) -> f32 {
+        let distance_penalty = self.edit_distance.unwrap_or(0) as f32;
+        let frequency_bonus = ((self.score + 1) as f64).log2() as f32;
+        distance_penalty - frequency_bonus
+    }
+}
+
+/// Read-only context a `Criterion` can use to rank candidates, beyond what's
+/// already carried on each `Candidate`.
+pub struct RankingContext<'a> {
+    pub preedit: &'a str,
+    pub context_model: &'a ContextModel,
+}
+
+/// One stage of the ranking pipeline. `rank` receives the candidates produced
+/// (and possibly already ordered) by earlier criteria and refines that order;
+/// ties left by one criterion are broken by the next.
+pub trait Criterion {
+    fn rank(&self, ctx: &RankingContext, candidates: &mut Vec<Candidate>);
+}
+
+/// Exact trie-prefix matches beat fuzzy ones, regardless of frequency.
+pub struct ExactPrefix;
+
+impl Criterion for ExactPrefix {
+    fn rank(&self, _ctx: &RankingContext, candidates: &mut Vec<Candidate>) {
+        candidates.sort_by_key(|c| c.edit_distance.unwrap_or(0) > 0);
+    }
+}
+
+/// Fewer SymSpell edits rank higher; candidates with no fuzzy distance at all
+/// (exact or literal matches) are treated as distance 0.
+pub struct Typo;
+
+impl Criterion for Typo {
+    fn rank(&self, _ctx: &RankingContext, candidates: &mut Vec<Candidate>) {
+        candidates.sort_by_key(|c| c.edit_distance.unwrap_or(0));
+    }
+}
+
+/// Boosts candidates that commonly follow the last confirmed word.
+pub struct Context;
+
+impl Criterion for Context {
+    fn rank(&self, ctx: &RankingContext, candidates: &mut Vec<Candidate>) {
+        let Some(prev_word_id) = ctx.context_model.history().last().copied() else {
+            return;
+        };
+        candidates.sort_by_key(|c| {
+            let boost = c
+                .word_id
+                .map_or(0, |id| ctx.context_model.bigram_boost(prev_word_id, id));
+            std::cmp::Reverse(boost)
+        });
+    }
+}
+
+/// Final tiebreaker: raw confirmed-word frequency.
+pub struct Frequency;
+
+impl Criterion for Frequency {
+    fn rank(&self, _ctx: &RankingContext, candidates: &mut Vec<Candidate>) {
+        candidates.sort_by_key(|c| std::cmp::Reverse(c.score));
+    }
+}
+
+/// The default chain: exact-prefix quality first, then typo distance, then
+/// context, with frequency as the last tiebreaker.
+pub fn default_criteria() -> Vec<Box<dyn Criterion>> {
+    vec![
+        Box::new(ExactPrefix),
+        Box::new(Typo),
+        Box::new(Context),
+        Box::new(Frequency),
+    ]
+}
+
+/// Runs a criterion chain over `candidates`, producing a single final order.
+///
+/// `candidates.sort_by_key` is stable, so to have the *first* criterion in
+/// `criteria` behave as the primary sort key (with each later criterion only
+/// breaking its ties) the chain must be applied least-significant-first: we
+/// iterate in reverse, letting the final sort (the first criterion) dominate
+/// while preserving the relative order later criteria already established for
+/// anything it considers equal.
+pub fn apply_criteria(criteria: &[Box<dyn Criterion>], ctx: &RankingContext, candidates: &mut Vec<Candidate>) {
+    for criterion in criteria.iter().rev() {
+        criterion.rank(ctx, candidates);
+    }
+}
+
+/// Tunable weights for the linear scoring model `score_candidate` combines
+/// sub-scores with. Where the `Criterion` chain above breaks ties
+/// lexicographically (stage A always beats stage B), this produces a single
+/// explainable number per candidate so two signals can actually be traded off
+/// against each other - see `ScoreDetails`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScoringWeights {
+    pub context: f32,
+    pub frequency: f32,
+    pub edit_distance: f32,
+    pub source_prior: f32,
+}
+
+impl ScoringWeights {
+    pub fn new() -> Self {
+        Self { context: 1.0, frequency: 1.0, edit_distance: 1.0, source_prior: 1.0 }
+    }
+
+    pub fn with_context(mut self, context: f32) -> Self {
+        self.context = context;
+        self
+    }
+
+    pub fn with_frequency(mut self, frequency: f32) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    pub fn with_edit_distance(mut self, edit_distance: f32) -> Self {
+        self.edit_distance = edit_distance;
+        self
+    }
+
+    pub fn with_source_prior(mut self, source_prior: f32) -> Self {
+        self.source_prior = source_prior;
+        self
+    }
+}
+
+impl Default for ScoringWeights {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One sub-score and the weight applied to it, so `ScoreDetails` can be
+/// rendered generically (debug logging, FFI JSON) without re-deriving which
+/// field means what.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct WeightedScore {
+    pub raw: f32,
+    pub weight: f32,
+}
+
+impl WeightedScore {
+    pub fn contribution(&self) -> f32 {
+        self.raw * self.weight
+    }
+}
+
+/// A breakdown of how `score_candidate` arrived at a candidate's final
+/// score, so integrators can debug ranking and users can see *why* a
+/// suggestion ranked where it did.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ScoreDetails {
+    pub context: WeightedScore,
+    pub frequency: WeightedScore,
+    /// Stored as a positive penalty; subtracted in `total`.
+    pub edit_distance_penalty: WeightedScore,
+    pub source_prior: WeightedScore,
+}
+
+impl ScoreDetails {
+    pub fn total(&self) -> f32 {
+        self.context.contribution() + self.frequency.contribution() + self.source_prior.contribution()
+            - self.edit_distance_penalty.contribution()
+    }
+}
+
+/// A ranked suggestion as returned by `ImeEngine::get_suggestions_explained`,
+/// pairing the candidate's surface form with the score breakdown that
+/// produced its rank.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScoredSuggestion {
+    pub nepali: String,
+    pub score: ScoreDetails,
+}
+
+/// Computes `candidate`'s weighted sub-scores against `ctx`.
+///
+/// `source_prior` is a parameter rather than something read off `Candidate`
+/// because it reflects how much to trust the *stage* a candidate was
+/// gathered from (trie vs fuzzy vs literal transliteration) - only the
+/// caller assembling candidates from multiple stages knows that.
+pub fn score_candidate(
+    candidate: &Candidate,
+    source_prior: f32,
+    ctx: &RankingContext,
+    weights: &ScoringWeights,
+) -> ScoreDetails {
+    let context_raw = match (ctx.context_model.history().last(), candidate.word_id) {
+        (Some(&prev_word_id), Some(word_id)) => ctx.context_model.bigram_boost(prev_word_id, word_id) as f32,
+        _ => 0.0,
+    };
+    let frequency_raw = ((candidate.score + 1) as f64).log2() as f32;
+    let edit_distance_raw = candidate.edit_distance.unwrap_or(0) as f32;
+
+    ScoreDetails {
+        context: WeightedScore { raw: context_raw, weight: weights.context },
+        frequency: WeightedScore { raw: frequency_raw, weight: weights.frequency },
+        edit_distance_penalty: WeightedScore { raw: edit_distance_raw, weight: weights.edit_distance },
+        source_prior: WeightedScore { raw: source_prior, weight: weights.source_prior },
+    }
+}
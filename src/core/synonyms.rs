@@ -0,0 +1,34 @@
+// File: src/core/synonyms.rs
+use crate::core::types::WordId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Maps user- or config-declared aliases (alternate romanizations or spellings,
+/// e.g. "cha"/"chha"/"xa") to the canonical `WordId` they should collapse to.
+///
+/// This is distinct from `WordMetadata.variants`: variants are spellings the
+/// learning engine has seen typed for a word it already knows, while a synonym
+/// is a declared equivalence between an input string and a *different*
+/// canonical word, so a rarely-typed alias inherits the canonical form's
+/// combined popularity instead of building up its own, separate frequency.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SynonymTable {
+    aliases: HashMap<String, WordId>,
+}
+
+impl SynonymTable {
+    pub fn new() -> Self {
+        Self { aliases: HashMap::new() }
+    }
+
+    /// Declares `alias` as collapsing to `canonical`.
+    pub fn add(&mut self, alias: &str, canonical: WordId) {
+        self.aliases.insert(alias.to_string(), canonical);
+    }
+
+    /// Resolves `key` (a Roman or Devanagari string) to its canonical `WordId`,
+    /// if it's a declared alias.
+    pub fn resolve(&self, key: &str) -> Option<WordId> {
+        self.aliases.get(key).copied()
+    }
+}
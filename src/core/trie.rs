@@ -1,8 +1,18 @@
 // File: src/core/trie.rs
 use crate::core::types::{WordId, WordMetadata};
+use crate::fuzzy::levenshtein_automaton::{AutomatonState, LevenshteinAutomaton};
 use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "std")]
 use std::collections::{BinaryHeap, HashMap, HashSet};
 
+#[cfg(not(feature = "std"))]
+use alloc::collections::BinaryHeap;
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+#[cfg(not(feature = "std"))]
+use hashbrown::{HashMap, HashSet};
+
 #[derive(Clone, Serialize, Deserialize)]
 struct Node {
     children: HashMap<u8, usize>,
@@ -21,12 +31,12 @@ impl Node {
 }
 
 #[derive(Clone, Serialize, Deserialize)]
-pub struct Trie {
+pub struct TrieBuilder {
     nodes: Vec<Node>,
     pub metadata_store: Vec<WordMetadata>,
 }
 
-impl Trie {
+impl TrieBuilder {
     pub fn new() -> Self {
         Self {
             nodes: vec![Node::new()],
@@ -34,16 +44,16 @@ impl Trie {
         }
     }
 
-    pub fn find_word_id_by_devanagari(&self, devanagari: &str) -> Option<WordId> {
-        self.metadata_store.iter().position(|meta| meta.devanagari == devanagari)
+    pub fn find_word_id_by_nepali(&self, nepali: &str) -> Option<WordId> {
+        self.metadata_store.iter().position(|meta| meta.nepali == nepali)
     }
 
-    pub fn get_or_create_metadata(&mut self, devanagari: &str) -> WordId {
-        if let Some(id) = self.find_word_id_by_devanagari(devanagari) {
+    pub fn get_or_create_metadata(&mut self, nepali: &str) -> WordId {
+        if let Some(id) = self.find_word_id_by_nepali(nepali) {
             id
         } else {
             let new_meta = WordMetadata {
-                devanagari: devanagari.to_string(),
+                nepali: nepali.to_string(),
                 frequency: 0,
                 variants: HashSet::new(),
             };
@@ -52,16 +62,11 @@ impl Trie {
         }
     }
 
-    // Corrected the unused variable warning
     pub fn insert(&mut self, key: &str, word_id: WordId, _frequency: u64) {
         let mut node_idx = 0;
         let mut path = vec![0];
 
         for &byte in key.as_bytes() {
-            // --- BORROW CHECKER FIX IS HERE ---
-            // The original code held a mutable borrow on a node while trying to modify the
-            // parent `nodes` vector, which is not allowed. This new structure performs
-            // the check and the modification in separate steps, respecting the borrow checker.
             let next_idx = if let Some(&child_idx) = self.nodes[node_idx].children.get(&byte) {
                 child_idx
             } else {
@@ -70,7 +75,6 @@ impl Trie {
                 self.nodes[node_idx].children.insert(byte, new_node_idx);
                 new_node_idx
             };
-            // --- END OF FIX ---
             node_idx = next_idx;
             path.push(node_idx);
         }
@@ -87,7 +91,7 @@ impl Trie {
                 .map(|&child_idx| self.nodes[child_idx].max_freq_in_subtree)
                 .max()
                 .unwrap_or(0);
-            
+
             let new_max_freq = current_node_freq.max(max_child_freq);
 
             if new_max_freq == self.nodes[idx].max_freq_in_subtree {
@@ -136,4 +140,79 @@ impl Trie {
             }
         }
     }
-}
\ No newline at end of file
+
+    /// Walks the trie and a Levenshtein automaton in lockstep, pruning an
+    /// entire subtree the moment the automaton proves it can no longer reach
+    /// an accepting state (`AutomatonState::can_match` goes false). Unlike
+    /// `SymSpell::lookup_bounded`, which generates a bounded candidate set up
+    /// front, this scales with the automaton's max edit distance rather than
+    /// the dictionary's size - useful once the trie holds more words than a
+    /// delete-table can comfortably enumerate.
+    ///
+    /// Runs in prefix mode: only *some prefix* of a trie key needs to be
+    /// within `automaton`'s max distance of the query, so keys longer than
+    /// the query can still match (e.g. querying "nam" can surface "namaste").
+    pub fn fuzzy_search(
+        &self,
+        automaton: &LevenshteinAutomaton,
+        start_state: &AutomatonState,
+    ) -> Vec<(WordId, usize)> {
+        let mut results = Vec::new();
+        self.fuzzy_search_node(0, automaton, start_state, &mut results);
+        results
+    }
+
+    fn fuzzy_search_node(
+        &self,
+        node_idx: usize,
+        automaton: &LevenshteinAutomaton,
+        state: &AutomatonState,
+        results: &mut Vec<(WordId, usize)>,
+    ) {
+        let node = &self.nodes[node_idx];
+
+        if let Some(id) = node.word_id {
+            if let Some(distance) = automaton.prefix_match_distance(state) {
+                results.push((id, distance));
+            }
+        }
+
+        if !automaton.can_match(state) {
+            return;
+        }
+
+        for (&byte, &child_idx) in &node.children {
+            // The trie is keyed on raw UTF-8 bytes, so this only yields a true
+            // Unicode `char` for the ASCII romanized keys it's actually built
+            // from; a multi-byte key would need to be walked a node at a time
+            // per byte instead, which `fuzzy_search` doesn't attempt.
+            let next_state = automaton.step(state, byte as char);
+            self.fuzzy_search_node(child_idx, automaton, &next_state, results);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fuzzy::levenshtein_automaton::LevenshteinAutomatonBuilder;
+
+    #[test]
+    fn prefix_fuzzy_search_surfaces_longer_dictionary_entries() {
+        let mut trie = TrieBuilder::new();
+        let word_id = trie.get_or_create_metadata("namaste");
+        trie.insert("namaste", word_id, 1);
+
+        for max_distance in 0..=2 {
+            let builder = LevenshteinAutomatonBuilder::new(max_distance);
+            let query: Vec<char> = "nam".chars().collect();
+            let automaton = builder.build(&query);
+            let matches = trie.fuzzy_search(&automaton, &automaton.start());
+            assert!(
+                matches.iter().any(|&(id, _)| id == word_id),
+                "querying \"nam\" at max_distance {} should surface \"namaste\"",
+                max_distance
+            );
+        }
+    }
+}
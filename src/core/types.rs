@@ -1,7 +1,13 @@
 // src/core/types.rs
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
 use std::collections::HashSet;
 
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+#[cfg(not(feature = "std"))]
+use hashbrown::HashSet;
+
 /// A unique identifier for a canonical Nepali word.
 pub type WordId = usize;
 
@@ -20,4 +26,7 @@ pub struct WordMetadata {
 
 /// A new model to store transliteration probabilities, P(R|N).
 /// Maps a (Roman String, Nepali WordId) pair to its co-occurrence frequency.
-pub type TransliterationModel = std::collections::HashMap<(String, WordId), u64>;
\ No newline at end of file
+#[cfg(feature = "std")]
+pub type TransliterationModel = std::collections::HashMap<(String, WordId), u64>;
+#[cfg(not(feature = "std"))]
+pub type TransliterationModel = hashbrown::HashMap<(String, WordId), u64>;
\ No newline at end of file
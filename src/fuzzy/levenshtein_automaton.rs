@@ -0,0 +1,119 @@
+// File: src/fuzzy/levenshtein_automaton.rs
+//
+// A reusable Levenshtein (Damerau) automaton for fuzzy trie search. Rather
+// than materializing a delete-variant table like `SymSpell` does, this walks
+// the query one character at a time alongside a DP row, so a dictionary trie
+// can be explored in lockstep: whole subtrees are skipped the moment the row
+// proves no descendant can come within `max_distance` of the query.
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Builds `LevenshteinAutomaton`s for a fixed max edit distance. Distinct from
+/// `LevenshteinAutomaton` itself so the (cheap) `max_distance` setting can be
+/// reused across many queries without re-threading it through every call.
+#[derive(Clone, Copy)]
+pub struct LevenshteinAutomatonBuilder {
+    max_distance: usize,
+}
+
+impl LevenshteinAutomatonBuilder {
+    pub fn new(max_distance: usize) -> Self {
+        Self { max_distance }
+    }
+
+    /// Compiles `query` into an automaton in prefix mode (trailing dictionary
+    /// characters past the query's length are free, so shorter queries can
+    /// still match longer dictionary entries).
+    pub fn build<'a>(&self, query: &'a [char]) -> LevenshteinAutomaton<'a> {
+        LevenshteinAutomaton { query, max_distance: self.max_distance }
+    }
+}
+
+/// A query compiled against a fixed max edit distance. `start()` produces the
+/// root `AutomatonState`; `step()` advances it by one trie-edge character.
+pub struct LevenshteinAutomaton<'a> {
+    query: &'a [char],
+    max_distance: usize,
+}
+
+/// The automaton's position after consuming some prefix of trie keys. This is
+/// the classic Levenshtein DP row, plus the previous row and last-consumed
+/// character needed to detect transpositions a la Damerau-Levenshtein, plus
+/// the best full-query distance seen so far along this path (see
+/// `best_prefix_distance`).
+#[derive(Clone)]
+pub struct AutomatonState {
+    row: Vec<usize>,
+    prev_row: Option<Vec<usize>>,
+    last_char: Option<char>,
+    best_prefix_distance: usize,
+}
+
+impl<'a> LevenshteinAutomaton<'a> {
+    pub fn start(&self) -> AutomatonState {
+        let row: Vec<usize> = (0..=self.query.len()).collect();
+        let best_prefix_distance = row[self.query.len()];
+        AutomatonState { row, prev_row: None, last_char: None, best_prefix_distance }
+    }
+
+    /// Advances `state` by one consumed character `c`, returning the new row.
+    pub fn step(&self, state: &AutomatonState, c: char) -> AutomatonState {
+        let n = self.query.len();
+        let mut next = Vec::with_capacity(n + 1);
+        next.push(state.row[0] + 1);
+
+        for j in 1..=n {
+            let cost = if self.query[j - 1] == c { 0 } else { 1 };
+            let mut value = (state.row[j] + 1) // deletion
+                .min(next[j - 1] + 1) // insertion
+                .min(state.row[j - 1] + cost); // substitution
+
+            if j > 1 {
+                if let (Some(prev_row), Some(last_char)) = (&state.prev_row, state.last_char) {
+                    if c == self.query[j - 2] && last_char == self.query[j - 1] {
+                        value = value.min(prev_row[j - 2] + 1); // transposition
+                    }
+                }
+            }
+
+            next.push(value);
+        }
+
+        let best_prefix_distance = state.best_prefix_distance.min(next[n]);
+        AutomatonState {
+            row: next,
+            prev_row: Some(state.row.clone()),
+            last_char: Some(c),
+            best_prefix_distance,
+        }
+    }
+
+    /// True while some descendant of `state` could still land within
+    /// `max_distance` of the query - the row minimum is a standard lower
+    /// bound on any edit distance reachable by extending the consumed path
+    /// further, so once it exceeds `max_distance` the whole subtree is dead.
+    ///
+    /// In prefix mode this alone over-prunes: once `best_prefix_distance`
+    /// has already matched the full query, every remaining dictionary
+    /// character is a free suffix extension, so the row keeps growing even
+    /// though the subtree is still a valid completion. Once a prefix match
+    /// is locked in, keep the subtree open regardless of the row.
+    pub fn can_match(&self, state: &AutomatonState) -> bool {
+        state.best_prefix_distance <= self.max_distance
+            || state.row.iter().min().copied().unwrap_or(usize::MAX) <= self.max_distance
+    }
+
+    /// In prefix mode, once some ancestor of `state` has matched the full
+    /// query within `max_distance`, every descendant is still an acceptable
+    /// completion - appending more dictionary characters beyond the matched
+    /// query is free. `best_prefix_distance` is exactly that: the smallest
+    /// full-query distance seen anywhere along the path to `state`.
+    pub fn prefix_match_distance(&self, state: &AutomatonState) -> Option<usize> {
+        if state.best_prefix_distance <= self.max_distance {
+            Some(state.best_prefix_distance)
+        } else {
+            None
+        }
+    }
+}
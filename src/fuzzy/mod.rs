@@ -0,0 +1,4 @@
+// File: src/fuzzy/mod.rs
+pub mod levenshtein_automaton;
+pub mod subsequence;
+pub mod symspell;
@@ -0,0 +1,131 @@
+// File: src/fuzzy/subsequence.rs
+//
+// A subsequence matcher for learned words: lets a user recall a word they've
+// taught the IME by typing a sparse sketch of it - skipping letters, the way
+// fuzzy-finder tools like fzf do - rather than a contiguous typo. This is a
+// different failure mode than `SymSpell`/the Levenshtein automaton, which
+// look for missing/extra/transposed *adjacent* characters, not an arbitrary
+// skip pattern, so it's a separate, narrower index rather than another mode
+// of either. It only indexes words `LearningEngine::learn` confirms, since a
+// sparse-sketch match against the whole bundled dictionary would be far too
+// permissive to be useful.
+use crate::core::types::WordId;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+#[cfg(not(feature = "std"))]
+use alloc::string::{String, ToString};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+#[cfg(not(feature = "std"))]
+use hashbrown::HashMap;
+
+/// A 64-bit bitmask of which ASCII letters a word contains (case-folded;
+/// one bit per `'a'..='z'`), built once per `add_word`. Checking whether a
+/// candidate's bag contains a query's bag is a single bitwise AND and rules
+/// out any candidate that's missing a letter the query needs - cheaper than
+/// the O(len) subsequence walk it gates, and a necessary (if weaker-than-
+/// count) condition for one string to be a subsequence of another.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct CharBag(u64);
+
+impl CharBag {
+    fn new(word: &str) -> Self {
+        let mut bits = 0u64;
+        for c in word.chars() {
+            if c.is_ascii_alphabetic() {
+                bits |= 1 << (c.to_ascii_lowercase() as u32 - 'a' as u32);
+            }
+        }
+        CharBag(bits)
+    }
+
+    /// True if every letter set in `query`'s bag is also set in `self`'s.
+    fn is_superset_of(&self, query: &CharBag) -> bool {
+        self.0 & query.0 == query.0
+    }
+}
+
+/// Indexes learned words by their character bag, so `lookup` can cheaply
+/// prefilter candidates before running the true subsequence check.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SubsequenceMatcher {
+    words: HashMap<WordId, (String, CharBag)>,
+}
+
+impl SubsequenceMatcher {
+    pub fn new() -> Self {
+        Self { words: HashMap::new() }
+    }
+
+    /// Indexes `word` (a learned word's Roman spelling) under `word_id`.
+    pub fn add_word(&mut self, word: &str, word_id: WordId) {
+        self.words.insert(word_id, (word.to_string(), CharBag::new(word)));
+    }
+
+    /// Returns every indexed word that contains `query` as a subsequence
+    /// (its characters appear in order, not necessarily contiguously), after
+    /// first discarding any candidate the char-bag prefilter proves can't
+    /// match. Order is unspecified; the caller (`ImeEngine`) ranks results
+    /// alongside its other suggestion stages.
+    pub fn lookup(&self, query: &str) -> Vec<WordId> {
+        if query.is_empty() {
+            return Vec::new();
+        }
+
+        let query_chars: Vec<char> = query.chars().collect();
+        let query_bag = CharBag::new(query);
+
+        self.words
+            .iter()
+            .filter(|(_, (_, bag))| bag.is_superset_of(&query_bag))
+            .filter(|(_, (word, _))| is_subsequence(&query_chars, word))
+            .map(|(&word_id, _)| word_id)
+            .collect()
+    }
+}
+
+impl Default for SubsequenceMatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// True if every character in `query`, in order, appears somewhere in
+/// `word` - not necessarily contiguously.
+fn is_subsequence(query: &[char], word: &str) -> bool {
+    let mut remaining = query.iter();
+    let Some(mut next) = remaining.next() else { return true };
+
+    for c in word.chars() {
+        if c == *next {
+            match remaining.next() {
+                Some(n) => next = n,
+                None => return true,
+            }
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_surfaces_a_dropped_vowel_sketch() {
+        let mut matcher = SubsequenceMatcher::new();
+        matcher.add_word("namaste", 0);
+        assert_eq!(matcher.lookup("nmste"), vec![0]);
+    }
+
+    #[test]
+    fn char_bag_prefilter_rejects_a_missing_letter() {
+        let bag = CharBag::new("namaste");
+        let query = CharBag::new("nmsz"); // 'z' never appears in "namaste"
+        assert!(!bag.is_superset_of(&query));
+    }
+}
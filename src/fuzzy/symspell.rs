@@ -1,8 +1,17 @@
 // File: src/fuzzy/symspell.rs
 use crate::core::types::WordId;
 use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "std")]
 use std::collections::{HashMap, HashSet};
 
+#[cfg(not(feature = "std"))]
+use alloc::string::{String, ToString};
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+#[cfg(not(feature = "std"))]
+use hashbrown::{HashMap, HashSet};
+
 /// A high-performance fuzzy search and spelling correction engine based on the
 /// Symmetric Delete (SymSpell) algorithm. It pre-calculates a dictionary of "deletes"
 /// for O(1) lookup complexity (relative to dictionary size).
@@ -11,6 +20,9 @@ pub struct SymSpell {
     /// Maps a delete variant (e.g., "namste") to the list of original WordIds
     /// it could have come from (e.g., [id_for_namaste]).
     deletes: HashMap<String, HashSet<WordId>>,
+    /// Canonical string for each indexed word, used to compute the true edit
+    /// distance against raw delete-collision candidates.
+    words: HashMap<WordId, String>,
     max_edit_distance: usize,
 }
 
@@ -18,6 +30,7 @@ impl SymSpell {
     pub fn new(max_edit_distance: usize) -> Self {
         Self {
             deletes: HashMap::new(),
+            words: HashMap::new(),
             max_edit_distance,
         }
     }
@@ -30,36 +43,130 @@ impl SymSpell {
         for edit in edits {
             self.deletes.entry(edit).or_default().insert(word_id);
         }
+        self.words.insert(word_id, word.to_string());
     }
 
-    /// Looks up a potentially misspelled word by generating its deletes and
-    /// finding them in the pre-calculated dictionary.
-    /// Complexity: O(k^2) where k is the input length. Crucially, this is
-    /// independent of the main dictionary size, making it extremely fast.
-    pub fn lookup(&self, input: &str) -> HashSet<WordId> {
-        let mut candidates = HashSet::new();
-        
-        // Check for an exact match first
+    /// Looks up a potentially misspelled word and returns every candidate within
+    /// `max_edit_distance`, ranked by true edit distance ascending.
+    ///
+    /// The symmetric-delete table is only a *candidate generator* - two unrelated
+    /// words can share a delete variant - so every raw hit is re-scored against the
+    /// input using Damerau-Levenshtein distance and anything over the configured
+    /// threshold is discarded. Ties in distance are left to the caller (e.g.
+    /// `ImeEngine`) to break using frequency or other context.
+    pub fn lookup(&self, input: &str) -> Vec<(WordId, usize)> {
+        self.lookup_bounded(input, None)
+    }
+
+    /// Same as `lookup`, but stops gathering raw delete-collision candidates
+    /// once `node_pool_size` of them have been seen, bounding the worst-case
+    /// memory/CPU a single query can spend expanding fuzzy nodes. `None`
+    /// means unbounded, matching `lookup`'s behavior.
+    pub fn lookup_bounded(&self, input: &str, node_pool_size: Option<usize>) -> Vec<(WordId, usize)> {
+        let mut raw_candidates = HashSet::new();
+        let within_budget = |n: usize| match node_pool_size {
+            Some(budget) => n < budget,
+            None => true,
+        };
+
+        // Exact match against the raw input.
         if let Some(word_ids) = self.deletes.get(input) {
             for &id in word_ids {
-                candidates.insert(id);
+                if !within_budget(raw_candidates.len()) {
+                    break;
+                }
+                raw_candidates.insert(id);
             }
         }
 
-        // Check for matches within the edit distance
+        // Matches within the edit distance via the delete-variant table.
         let edits = self.generate_edits(input);
-        for edit in edits {
+        'gather: for edit in edits {
             if let Some(word_ids) = self.deletes.get(&edit) {
                 for &id in word_ids {
-                    candidates.insert(id);
+                    if !within_budget(raw_candidates.len()) {
+                        break 'gather;
+                    }
+                    raw_candidates.insert(id);
+                }
+            }
+        }
+
+        let input_chars: Vec<char> = input.chars().collect();
+        let mut ranked: Vec<(WordId, usize)> = Vec::with_capacity(raw_candidates.len());
+        for word_id in raw_candidates {
+            let Some(candidate) = self.words.get(&word_id) else { continue };
+            let candidate_chars: Vec<char> = candidate.chars().collect();
+            if let Some(distance) =
+                Self::damerau_levenshtein(&input_chars, &candidate_chars, self.max_edit_distance)
+            {
+                ranked.push((word_id, distance));
+            }
+        }
+
+        // Distance ascending first (exact matches always lead), frequency is the
+        // caller's job since this module doesn't own `WordMetadata`.
+        ranked.sort_by_key(|&(_, distance)| distance);
+        ranked
+    }
+
+    /// Computes the Damerau-Levenshtein distance between two Unicode scalar
+    /// sequences, returning `None` as soon as it's provable the distance exceeds
+    /// `max_distance` (every entry in the row already exceeds it).
+    fn damerau_levenshtein(a: &[char], b: &[char], max_distance: usize) -> Option<usize> {
+        let (m, n) = (a.len(), b.len());
+        if m.abs_diff(n) > max_distance {
+            return None;
+        }
+        if m == 0 && n == 0 {
+            return Some(0);
+        }
+
+        let mut d = vec![vec![0usize; n + 1]; m + 1];
+        for i in 0..=m {
+            d[i][0] = i;
+        }
+        for j in 0..=n {
+            d[0][j] = j;
+        }
+
+        for i in 1..=m {
+            let mut row_min = usize::MAX;
+            for j in 1..=n {
+                let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+                let mut value = (d[i - 1][j] + 1) // deletion
+                    .min(d[i][j - 1] + 1) // insertion
+                    .min(d[i - 1][j - 1] + cost); // substitution
+
+                if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                    value = value.min(d[i - 2][j - 2] + 1); // transposition
                 }
+
+                d[i][j] = value;
+                row_min = row_min.min(value);
+            }
+
+            // Early-exit: even the best cell in this row already exceeds the budget,
+            // so every subsequent row can only grow from here.
+            if row_min > max_distance {
+                return None;
             }
         }
-        candidates
+
+        let distance = d[m][n];
+        if distance <= max_distance {
+            Some(distance)
+        } else {
+            None
+        }
     }
 
     /// Generates all unique string variants within the max_edit_distance.
     /// This includes the original string itself.
+    ///
+    /// Deletes are taken by Unicode scalar value (`chars()`), not byte index -
+    /// Devanagari codepoints are multi-byte in UTF-8, so indexing/removing by
+    /// byte offset would split one and panic on a non-char-boundary.
     fn generate_edits(&self, word: &str) -> HashSet<String> {
         let mut edits = HashSet::new();
         edits.insert(word.to_string()); // Distance 0
@@ -69,16 +176,51 @@ impl SymSpell {
         for _ in 0..self.max_edit_distance {
             let mut next_edits = HashSet::new();
             for edit in current_edits {
-                for i in 0..edit.len() {
-                    let mut deleted_variant = edit.clone();
-                    deleted_variant.remove(i);
+                let chars: Vec<char> = edit.chars().collect();
+                for i in 0..chars.len() {
+                    let deleted_variant: String = chars
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(j, &c)| if j == i { None } else { Some(c) })
+                        .collect();
                     next_edits.insert(deleted_variant);
                 }
             }
             edits.extend(next_edits.clone());
             current_edits = next_edits;
         }
-        
+
         edits
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_word_does_not_panic_on_multi_byte_devanagari() {
+        let mut symspell = SymSpell::new(2);
+        symspell.add_word("रामस्", 0);
+        let results = symspell.lookup("रामस्");
+        assert!(results.iter().any(|&(id, distance)| id == 0 && distance == 0));
+    }
+
+    #[test]
+    fn lookup_ranks_by_true_damerau_levenshtein_distance() {
+        let mut symspell = SymSpell::new(2);
+        symspell.add_word("ram", 0);
+        symspell.add_word("raam", 1); // one insertion away from "ram"
+
+        // "rma" is a transposition of "ram" - Damerau-Levenshtein distance 1,
+        // not the 2 a plain (non-Damerau) Levenshtein would report for two
+        // substitutions.
+        let results = symspell.lookup("rma");
+        assert_eq!(results.first(), Some(&(0, 1)));
+
+        // Ascending by true distance even though "raam" and "ram" share a
+        // delete-variant collision in the underlying table.
+        let distances: Vec<usize> = results.iter().map(|&(_, d)| d).collect();
+        assert!(distances.windows(2).all(|w| w[0] <= w[1]));
+    }
+}
@@ -1,5 +1,6 @@
 // File: src/learning.rs
-use crate::core::{context::ContextModel, trie::Trie}; // MODIFIED
+use crate::core::{context::ContextModel, synonyms::SynonymTable, trie::TrieBuilder};
+use crate::fuzzy::subsequence::SubsequenceMatcher;
 use crate::fuzzy::symspell::SymSpell;
 
 pub struct LearningEngine {
@@ -18,13 +19,24 @@ impl LearningEngine {
 
     pub fn learn(
         &self,
-        trie: &mut Trie, // MODIFIED
+        trie: &mut TrieBuilder,
         context_model: &mut ContextModel,
         symspell: &mut SymSpell,
+        subsequence: &mut SubsequenceMatcher,
+        synonyms: &SynonymTable,
         confirmation: &WordConfirmation,
     ) {
-    let word_id = trie.get_or_create_metadata(&confirmation.devanagari);
-        
+        // If either the Roman spelling or the Devanagari form typed is a declared
+        // alias, confirming it should bump the canonical word instead of creating
+        // (or reinforcing) a separate dictionary entry for the alias.
+        let devanagari = synonyms
+            .resolve(&confirmation.roman)
+            .or_else(|| synonyms.resolve(&confirmation.devanagari))
+            .map(|canonical_id| trie.metadata_store[canonical_id].nepali.clone())
+            .unwrap_or_else(|| confirmation.devanagari.clone());
+
+        let word_id = trie.get_or_create_metadata(&devanagari);
+
         let metadata = &mut trie.metadata_store[word_id];
         metadata.frequency += self.frequency_increment;
         
@@ -35,9 +47,17 @@ impl LearningEngine {
             // indexing every single user-typed variant.
             symspell.add_word(&confirmation.roman, word_id);
             if metadata.variants.len() == 1 { // First time we see this word, add its Nepali form too
-                 symspell.add_word(&confirmation.devanagari, word_id);
+                 symspell.add_word(&devanagari, word_id);
             }
         }
+
+        // Also index by character bag for the subsequence matcher, so a
+        // sparse, skip-letter sketch of a word the user has specifically
+        // taught the IME can still recall it. Re-indexing on every
+        // confirmation (not just new variants) is cheap and keeps the bag
+        // in sync if the same word is ever confirmed under a different
+        // Roman spelling.
+        subsequence.add_word(&confirmation.roman, word_id);
         
         let updated_freq = metadata.frequency;
 
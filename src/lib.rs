@@ -1,9 +1,23 @@
 // File: src/lib.rs
+//
+// The ranking core (core::trie, core::context, core::types, fuzzy::symspell)
+// only touches alloc collections and serde, so it's usable from a `no_std`
+// target (e.g. a WASM or memory-constrained on-device keyboard) that supplies
+// its own storage and serialization. Everything that needs an OS - file I/O,
+// the CLI/ibus binaries, C FFI - lives behind the default `std` feature.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 pub mod core;
+pub mod fuzzy;
+
+#[cfg(feature = "std")]
+pub mod c_api;
+#[cfg(feature = "std")]
 pub mod learning;
+#[cfg(feature = "std")]
 pub mod persistence;
-pub mod c_api;
-pub mod fuzzy;
 
-pub use crate::core::engine::ImeEngine;
\ No newline at end of file
+#[cfg(feature = "std")]
+pub use crate::core::engine::ImeEngine;
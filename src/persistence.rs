@@ -1,17 +1,28 @@
 // File: src/persistence.rs
+use crate::core::context::ContextModel;
 use crate::core::engine::ImeEngine;
 use crate::core::trie::TrieBuilder;
+use crate::core::types::WordId;
+use crate::fuzzy::subsequence::SubsequenceMatcher;
 use crate::fuzzy::symspell::SymSpell;
+use std::collections::HashMap;
 use std::fs::{self, File};
-use std::io::{BufReader, BufWriter, Error, ErrorKind}; // <-- ADDED ErrorKind for clarity
+use std::io::{BufRead, BufReader, BufWriter, Error, ErrorKind}; // <-- ADDED ErrorKind for clarity
 use std::path::Path;
 use tempfile::NamedTempFile;
 
+/// A pretrained Nepali dictionary bundled with the crate so a fresh install has
+/// useful suggestions immediately instead of an empty trie. See
+/// `ImeEngine::from_file_or_new`.
+pub const BUNDLED_SEED_DICTIONARY: &str = include_str!("../assets/seed_dictionary.tsv");
+
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
 struct SerializableState {
     trie_builder: TrieBuilder,
     context_model: crate::core::context::ContextModel,
     symspell: SymSpell,
+    subsequence: SubsequenceMatcher,
+    synonyms: crate::core::synonyms::SynonymTable,
 }
 
 pub fn save_to_disk(engine: &ImeEngine, path: &Path) -> Result<(), Error> {
@@ -22,6 +33,8 @@ pub fn save_to_disk(engine: &ImeEngine, path: &Path) -> Result<(), Error> {
         trie_builder: engine.trie_builder.clone(),
         context_model: engine.context_model.clone(),
         symspell: engine.symspell.clone(),
+        subsequence: engine.subsequence.clone(),
+        synonyms: engine.synonyms.clone(),
     };
 
     let temp_file = NamedTempFile::new_in(parent_dir)?;
@@ -48,6 +61,227 @@ pub fn load_from_disk(path: &Path) -> Result<ImeEngine, Box<dyn std::error::Erro
     engine.trie_builder = state.trie_builder;
     engine.context_model = state.context_model;
     engine.symspell = state.symspell;
-    
+    engine.subsequence = state.subsequence;
+    engine.synonyms = state.synonyms;
+
     Ok(engine)
+}
+
+/// Bulk-loads a plain-text seed dictionary of `devanagari<TAB>roman<TAB>frequency`
+/// lines into the trie and fuzzy index in a single pass.
+///
+/// The input is expected to be sorted by the Devanagari column so that every
+/// roman variant of a word appears on consecutive lines; this lets the loader
+/// reuse the `WordId` it just created instead of re-scanning `metadata_store`
+/// with `find_word_id_by_nepali` for every line (O(n) per call, O(n^2) overall
+/// for a dictionary-sized file).
+pub fn load_seed_dictionary(
+    trie: &mut TrieBuilder,
+    symspell: &mut SymSpell,
+    reader: impl BufRead,
+) -> Result<(), Error> {
+    let mut last_devanagari: Option<String> = None;
+    let mut last_word_id = 0;
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut parts = line.splitn(3, '\t');
+        let (Some(devanagari), Some(roman), Some(frequency)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let frequency: u64 = frequency.trim().parse().unwrap_or(0);
+
+        let (word_id, is_new_word) = if last_devanagari.as_deref() == Some(devanagari) {
+            (last_word_id, false)
+        } else {
+            let id = trie.get_or_create_metadata(devanagari);
+            last_devanagari = Some(devanagari.to_string());
+            last_word_id = id;
+            (id, true)
+        };
+
+        let metadata = &mut trie.metadata_store[word_id];
+        metadata.frequency = metadata.frequency.max(frequency);
+        let updated_frequency = metadata.frequency;
+        let is_new_variant = metadata.variants.insert(roman.to_string());
+
+        trie.insert(roman, word_id, updated_frequency);
+        if is_new_variant {
+            symspell.add_word(roman, word_id);
+        }
+        // Index the Devanagari form itself once per word, so fuzzy lookup also
+        // matches on pasted/typed Devanagari, mirroring `LearningEngine::learn`.
+        if is_new_word {
+            symspell.add_word(devanagari, word_id);
+        }
+    }
+
+    Ok(())
+}
+
+/// How `import_dictionary` should interpret its input stream. See
+/// `ImeEngine::import_dictionary`.
+pub enum DictionaryFormat {
+    /// Plain `roman<TAB>nepali<TAB>frequency` lines, one entry per line.
+    FrequencyList,
+    /// A Hunspell-style word list: each line of the main reader is a Roman
+    /// word, optionally followed by a `/`-separated affix-flag suffix (we
+    /// don't apply Hunspell's affix rules, so it's stripped and ignored).
+    /// `info` holds the parallel `.info` file's contents - `roman<TAB>frequency`
+    /// lines - used to seed frequencies; words with no matching line default
+    /// to a frequency of 1. Without a real dictionary to transliterate into
+    /// Devanagari, each word's own Roman spelling is seeded as its canonical
+    /// form too, to be corrected the first time a user confirms the real one.
+    Hunspell { info: String },
+}
+
+/// Bulk-imports an external dictionary in `format`, seeding `trie` and
+/// `symspell` (and, if `seed_context` is set, `context_model`'s bigram table)
+/// in one pass. Unlike `load_seed_dictionary` (which expects this crate's own
+/// sorted `devanagari<TAB>roman<TAB>frequency` seed format), this accepts
+/// resources harvested from existing Nepali spelling tools, so a fresh
+/// install isn't limited to the bundled seed dictionary. Returns the number
+/// of words imported.
+///
+/// `seed_context` feeds each imported word through `ContextModel::add_word`,
+/// the same call `LearningEngine::learn` makes for a user-confirmed word.
+/// Bulk dictionaries aren't real sentences, so this does build some spurious
+/// bigrams between adjacent, unrelated entries - leave it off for large or
+/// unordered lists and only enable it for small, curated ones (e.g. common
+/// phrase fragments) where adjacency is meaningful.
+pub fn import_dictionary(
+    trie: &mut TrieBuilder,
+    symspell: &mut SymSpell,
+    context_model: &mut ContextModel,
+    reader: impl BufRead,
+    format: DictionaryFormat,
+    seed_context: bool,
+) -> Result<usize, Error> {
+    match format {
+        DictionaryFormat::FrequencyList => import_frequency_list(trie, symspell, context_model, reader, seed_context),
+        DictionaryFormat::Hunspell { info } => {
+            import_hunspell(trie, symspell, context_model, reader, &info, seed_context)
+        }
+    }
+}
+
+fn import_frequency_list(
+    trie: &mut TrieBuilder,
+    symspell: &mut SymSpell,
+    context_model: &mut ContextModel,
+    reader: impl BufRead,
+    seed_context: bool,
+) -> Result<usize, Error> {
+    let mut imported = 0;
+    // See `import_word`'s doc comment for why this cache exists - without it,
+    // every line falls back to `TrieBuilder::find_word_id_by_nepali`'s O(n)
+    // scan, making a bulk import O(n^2) in the size of the list.
+    let mut nepali_ids: HashMap<String, WordId> = HashMap::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut parts = line.splitn(3, '\t');
+        let (Some(roman), Some(nepali), Some(frequency)) = (parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+        let frequency: u64 = frequency.trim().parse().unwrap_or(1);
+
+        import_word(trie, symspell, context_model, &mut nepali_ids, roman, nepali, frequency, seed_context);
+        imported += 1;
+    }
+    Ok(imported)
+}
+
+fn import_hunspell(
+    trie: &mut TrieBuilder,
+    symspell: &mut SymSpell,
+    context_model: &mut ContextModel,
+    dict_reader: impl BufRead,
+    info: &str,
+    seed_context: bool,
+) -> Result<usize, Error> {
+    let mut frequencies: HashMap<String, u64> = info
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(2, '\t');
+            let word = parts.next()?;
+            let frequency: u64 = parts.next()?.trim().parse().ok()?;
+            Some((word.to_string(), frequency))
+        })
+        .collect();
+
+    let mut imported = 0;
+    let mut nepali_ids: HashMap<String, WordId> = HashMap::new();
+    for line in dict_reader.lines() {
+        let line = line?;
+        if line.is_empty() {
+            continue;
+        }
+
+        let roman = line.split('/').next().unwrap_or(&line).trim();
+        if roman.is_empty() {
+            continue;
+        }
+        let frequency = frequencies.remove(roman).unwrap_or(1);
+
+        import_word(trie, symspell, context_model, &mut nepali_ids, roman, roman, frequency, seed_context);
+        imported += 1;
+    }
+    Ok(imported)
+}
+
+/// Imports a single `roman`/`nepali` pair into `trie`/`symspell`/
+/// `context_model`. `nepali_ids` is the caller's per-import-call cache from
+/// Devanagari form to `WordId` - populated here instead of re-deriving it
+/// from `trie.get_or_create_metadata` (whose cache miss path is
+/// `find_word_id_by_nepali`'s O(n) scan over `metadata_store`) on every
+/// line, which is what made `import_frequency_list`/`import_hunspell` O(n^2)
+/// over the imported list. Mirrors `load_seed_dictionary`'s
+/// `last_devanagari`/`last_word_id` cache, generalized to the whole list
+/// rather than just consecutive lines, since this format isn't sorted by
+/// Devanagari form.
+fn import_word(
+    trie: &mut TrieBuilder,
+    symspell: &mut SymSpell,
+    context_model: &mut ContextModel,
+    nepali_ids: &mut HashMap<String, WordId>,
+    roman: &str,
+    nepali: &str,
+    frequency: u64,
+    seed_context: bool,
+) {
+    let word_id = match nepali_ids.get(nepali) {
+        Some(&id) => id,
+        None => {
+            let id = trie.get_or_create_metadata(nepali);
+            nepali_ids.insert(nepali.to_string(), id);
+            id
+        }
+    };
+    let is_new_variant = {
+        let metadata = &mut trie.metadata_store[word_id];
+        metadata.frequency = metadata.frequency.max(frequency);
+        metadata.variants.insert(roman.to_string())
+    };
+
+    trie.insert(roman, word_id, frequency);
+    if is_new_variant {
+        symspell.add_word(roman, word_id);
+    }
+    if roman != nepali {
+        symspell.add_word(nepali, word_id);
+    }
+
+    if seed_context {
+        context_model.add_word(word_id);
+    }
 }
\ No newline at end of file